@@ -0,0 +1,61 @@
+/// Generates the registry of all solutions in `src/bin/`, which is linked into the `all_days` binary.
+use std::{env, fs, io::Error, path::Path};
+
+/// Checks whether a file name is a solution, e.g. `2023_07.rs`.
+fn is_solution(name: &str) -> bool {
+    let Some(stem) = name.strip_suffix(".rs") else {
+        return false;
+    };
+
+    match stem.split_once('_') {
+        Some((year, day)) => {
+            year.len() == 4
+                && day.len() == 2
+                && year.chars().all(|c| c.is_ascii_digit())
+                && day.chars().all(|c| c.is_ascii_digit())
+        }
+        None => false,
+    }
+}
+
+fn main() -> Result<(), Error> {
+    println!("cargo::rerun-if-changed=src/bin");
+
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    let mut bins: Vec<String> = vec![];
+
+    // NOTE: with `dhat-heap`, every solution declares its own global allocator, so they can't be linked together.
+    if env::var_os("CARGO_FEATURE_DHAT_HEAP").is_none() && bin_dir.exists() {
+        for entry in fs::read_dir(&bin_dir)? {
+            let name = entry?.file_name().to_string_lossy().to_string();
+            if is_solution(&name) {
+                bins.push(name.trim_end_matches(".rs").to_string());
+            }
+        }
+    }
+
+    bins.sort_unstable();
+
+    let mut registry = String::from("// @generated by build.rs, do not edit.\n\n");
+
+    for bin in &bins {
+        let path = bin_dir.join(format!("{bin}.rs"));
+        registry.push_str(&format!(
+            "#[allow(dead_code)]\n#[path = {:?}]\nmod day_{bin};\n\n",
+            path.to_string_lossy()
+        ));
+    }
+
+    registry.push_str("pub static DAYS: &[advent_of_code::template::registry::Entry] = &[\n");
+    for bin in &bins {
+        registry.push_str(&format!(
+            "    advent_of_code::template::registry::Entry {{ day: day_{bin}::DAY, run: day_{bin}::run_day, timeout: day_{bin}::TIMEOUT }},\n"
+        ));
+    }
+    registry.push_str("];\n");
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("registry.rs"), registry)
+}
@@ -0,0 +1,102 @@
+//! Benchmarks the searches of `advent_of_code::search` on a generated maze.
+//!
+//! Run with `cargo run --release --example search`.
+
+use advent_of_code::grid::{Direction, Grid, Point};
+use advent_of_code::search::{astar, bfs, bfs_distances, brent, dijkstra, shortest_paths};
+use advent_of_code::template::runner::bench_fn;
+
+const SIZE: usize = 141;
+
+/// A maze of vertical walls on every fourth column, each with a single gap.
+fn maze() -> Grid<char> {
+    let mut grid = Grid::new(SIZE, SIZE, '.');
+    for x in (2..SIZE).step_by(4) {
+        let gap = (x * 37) % SIZE;
+        for y in (0..SIZE).filter(|y| *y != gap) {
+            grid[Point::new(x as i64, y as i64)] = '#';
+        }
+    }
+    grid
+}
+
+fn open_neighbors(grid: &Grid<char>, point: Point) -> impl Iterator<Item = Point> + '_ {
+    grid.neighbors4(point).filter(|p| grid[*p] != '#')
+}
+
+/// Moving costs 1 and turning costs 1000, as in the reindeer maze puzzles.
+fn reindeer_moves(
+    grid: &Grid<char>,
+    (point, dir): (Point, Direction),
+) -> Vec<((Point, Direction), u64)> {
+    let mut moves = vec![
+        ((point, dir.turn_left()), 1000),
+        ((point, dir.turn_right()), 1000),
+    ];
+    let next = point + dir;
+    if grid.get(next).is_some_and(|c| *c != '#') {
+        moves.push(((next, dir), 1));
+    }
+    moves
+}
+
+fn main() {
+    let grid = maze();
+    let end = Point::new(SIZE as i64 - 1, SIZE as i64 - 1);
+
+    bench_fn(
+        "bfs",
+        |grid| bfs(Point::ORIGIN, |p| open_neighbors(grid, *p), |p| *p == end),
+        &grid,
+    );
+
+    bench_fn(
+        "bfs_distances",
+        |grid| bfs_distances(Point::ORIGIN, |p| open_neighbors(grid, *p)),
+        &grid,
+    );
+
+    bench_fn(
+        "dijkstra",
+        |grid| {
+            dijkstra(
+                Point::ORIGIN,
+                |p| open_neighbors(grid, *p).map(|p| (p, 1_u64)),
+                |p| *p == end,
+            )
+        },
+        &grid,
+    );
+
+    bench_fn(
+        "astar",
+        |grid| {
+            astar(
+                Point::ORIGIN,
+                |p| open_neighbors(grid, *p).map(|p| (p, 1_u64)),
+                |p| p.manhattan(end),
+                |p| *p == end,
+            )
+        },
+        &grid,
+    );
+
+    bench_fn(
+        "shortest_paths",
+        |grid| {
+            shortest_paths(
+                (Point::ORIGIN, Direction::Right),
+                |s| reindeer_moves(grid, *s),
+                |s| s.0 == end,
+            )
+        },
+        &grid,
+    );
+
+    // x² + 1 modulo a prime enters a cycle after a tail, like the sequences of Pollard's rho.
+    bench_fn(
+        "brent",
+        |seed| brent(seed, |x: &u64| (x * x + 1) % 1_000_003),
+        7_u64,
+    );
+}
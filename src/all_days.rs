@@ -0,0 +1,171 @@
+/// Runs `all`, `time` and `verify` in-process, with every solution linked into this binary.
+use advent_of_code::template::commands::time::TimeOptions;
+use advent_of_code::template::commands::{all, time, verify};
+use advent_of_code::template::run_multi::MultiOptions;
+use advent_of_code::template::runner::RunOptions;
+use args::{AppArguments, parse};
+
+mod registry {
+    include!(concat!(env!("OUT_DIR"), "/registry.rs"));
+}
+
+mod args {
+    use advent_of_code::template::runner::OutputFormat;
+    use advent_of_code::template::{Day, Year};
+    use std::num::NonZeroUsize;
+    use std::time::Duration;
+    use std::{env, process};
+
+    /// Steps that got slower by more than this many percent are flagged by `time --compare`.
+    const DEFAULT_REGRESSION_THRESHOLD: f64 = 10.0;
+
+    pub enum AppArguments {
+        All {
+            year: Year,
+            format: OutputFormat,
+            jobs: NonZeroUsize,
+            timeout: Option<Duration>,
+        },
+        Time {
+            year: Year,
+            all: bool,
+            day: Option<Day>,
+            store: bool,
+            compare: Option<f64>,
+            format: OutputFormat,
+            timeout: Option<Duration>,
+        },
+        Verify {
+            year: Year,
+        },
+    }
+
+    fn opt_free_day(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Option<Day>, Box<dyn std::error::Error>> {
+        let day: Option<String> = args.opt_free_from_str()?;
+        Ok(day.map(|d| Day::from_str_in_year(&d, year)).transpose()?)
+    }
+
+    /// Reads the time limit in seconds from `--timeout`, falling back to the `AOC_TIMEOUT` environment variable.
+    fn timeout(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<Duration>, Box<dyn std::error::Error>> {
+        let seconds: Option<u64> = match args.opt_value_from_str("--timeout")? {
+            Some(seconds) => Some(seconds),
+            None => match env::var("AOC_TIMEOUT") {
+                Ok(seconds) => Some(seconds.parse().map_err(|_| {
+                    format!("expecting AOC_TIMEOUT to be a number of seconds, got `{seconds}`")
+                })?),
+                Err(_) => None,
+            },
+        };
+
+        Ok(seconds.map(Duration::from_secs))
+    }
+
+    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let mut args = pico_args::Arguments::from_env();
+
+        let subcommand = args.subcommand()?;
+
+        // NOTE: options need to be consumed before free arguments.
+        let year: Year = args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Year::configured);
+        let format: OutputFormat = args.opt_value_from_str("--format")?.unwrap_or_default();
+        let timeout = timeout(&mut args)?;
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => AppArguments::All {
+                year,
+                format,
+                jobs: args
+                    .opt_value_from_str("--jobs")?
+                    .unwrap_or(NonZeroUsize::MIN),
+                timeout,
+            },
+            Some("time") => {
+                let all = args.contains("--all");
+                let store = args.contains("--store");
+                let threshold: f64 = args
+                    .opt_value_from_str("--threshold")?
+                    .unwrap_or(DEFAULT_REGRESSION_THRESHOLD);
+                let compare = args.contains("--compare").then_some(threshold);
+
+                AppArguments::Time {
+                    year,
+                    all,
+                    day: opt_free_day(&mut args, year)?,
+                    store,
+                    compare,
+                    format,
+                    timeout,
+                }
+            }
+            Some("verify") => AppArguments::Verify { year },
+            Some(x) => {
+                eprintln!("Unknown command: {x}");
+                process::exit(1);
+            }
+            None => {
+                eprintln!("No command specified.");
+                process::exit(1);
+            }
+        };
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+        }
+
+        Ok(app_args)
+    }
+}
+
+fn main() {
+    match parse() {
+        Err(err) => {
+            eprintln!("Error: {err}");
+            std::process::exit(1);
+        }
+        Ok(args) => match args {
+            AppArguments::All {
+                year,
+                format,
+                jobs,
+                timeout,
+            } => {
+                let options = MultiOptions {
+                    run: RunOptions {
+                        format,
+                        ..RunOptions::default()
+                    },
+                    jobs,
+                    timeout,
+                };
+                all::handle(registry::DAYS, year, options);
+            }
+            AppArguments::Time {
+                year,
+                day,
+                all,
+                store,
+                compare,
+                format,
+                timeout,
+            } => {
+                let options = TimeOptions {
+                    all,
+                    store,
+                    compare,
+                    format,
+                    timeout,
+                };
+                time::handle(registry::DAYS, year, day, options);
+            }
+            AppArguments::Verify { year } => verify::handle(registry::DAYS, year),
+        },
+    };
+}
@@ -0,0 +1,758 @@
+//! A two-dimensional grid of cells, as most puzzles draw their maps, and points and directions on it.
+//!
+//! The origin is the top-left cell, `x` grows to the right and `y` grows downwards, like the lines of the puzzle input.
+
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Add, AddAssign, Index, IndexMut, Mul, Neg, Sub, SubAssign};
+use std::str::FromStr;
+
+/// A position on a grid. Coordinates are signed, so that stepping off the grid is representable.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: i64,
+    pub y: i64,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    #[must_use]
+    pub const fn new(x: i64, y: i64) -> Self {
+        Self { x, y }
+    }
+
+    /// The point one step away in `direction`.
+    #[must_use]
+    pub fn step(self, direction: Direction) -> Self {
+        self + direction.delta()
+    }
+
+    /// The four points that share an edge with this point: up, right, down, left.
+    pub fn neighbors4(self) -> impl Iterator<Item = Point> {
+        Direction::ALL.into_iter().map(move |d| self.step(d))
+    }
+
+    /// The eight points that share an edge or a corner with this point, clockwise from up.
+    pub fn neighbors8(self) -> impl Iterator<Item = Point> {
+        NEIGHBORS8.into_iter().map(move |delta| self + delta)
+    }
+
+    /// The taxicab distance to another point.
+    #[must_use]
+    pub fn manhattan(self, other: Point) -> u64 {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
+    }
+}
+
+const NEIGHBORS8: [Point; 8] = [
+    Point::new(0, -1),
+    Point::new(1, -1),
+    Point::new(1, 0),
+    Point::new(1, 1),
+    Point::new(0, 1),
+    Point::new(-1, 1),
+    Point::new(-1, 0),
+    Point::new(-1, -1),
+];
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl From<(i64, i64)> for Point {
+    fn from((x, y): (i64, i64)) -> Self {
+        Self { x, y }
+    }
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Point) -> Point {
+        Point::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, rhs: Direction) -> Point {
+        self.step(rhs)
+    }
+}
+
+impl AddAssign for Point {
+    fn add_assign(&mut self, rhs: Point) {
+        *self = *self + rhs;
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, rhs: Direction) {
+        *self = self.step(rhs);
+    }
+}
+
+impl Sub for Point {
+    type Output = Point;
+
+    fn sub(self, rhs: Point) -> Point {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl SubAssign for Point {
+    fn sub_assign(&mut self, rhs: Point) {
+        *self = *self - rhs;
+    }
+}
+
+impl Mul<i64> for Point {
+    type Output = Point;
+
+    fn mul(self, rhs: i64) -> Point {
+        Point::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl Neg for Point {
+    type Output = Point;
+
+    fn neg(self) -> Point {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the four directions on a grid.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise from up.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// The offset of one step in this direction.
+    #[must_use]
+    pub const fn delta(self) -> Point {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::Down => Point::new(0, 1),
+            Direction::Left => Point::new(-1, 0),
+        }
+    }
+
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Right => Direction::Up,
+            Direction::Down => Direction::Right,
+            Direction::Left => Direction::Down,
+        }
+    }
+
+    #[must_use]
+    pub const fn reverse(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = GridError;
+
+    /// Reads a direction as puzzles write it: an arrow (`^>v<`), a letter (`URDL`) or a compass point (`NESW`).
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '^' | 'U' | 'N' => Ok(Direction::Up),
+            '>' | 'R' | 'E' => Ok(Direction::Right),
+            'v' | 'D' | 'S' => Ok(Direction::Down),
+            '<' | 'L' | 'W' => Ok(Direction::Left),
+            _ => Err(GridError::InvalidDirection(c)),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// A line of the input is longer or shorter than the first line.
+    Ragged {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A cell could not be read. Lines and columns start at 1.
+    InvalidCell {
+        line: usize,
+        column: usize,
+        cell: char,
+    },
+    /// A character is not a direction.
+    InvalidDirection(char),
+}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Ragged {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {line} has {found} cells, expected {expected} like the first line."
+            ),
+            GridError::InvalidCell { line, column, cell } => {
+                write!(
+                    f,
+                    "unexpected cell `{cell}` at line {line}, column {column}."
+                )
+            }
+            GridError::InvalidDirection(c) => write!(f, "`{c}` is not a direction."),
+        }
+    }
+}
+
+impl Error for GridError {}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular grid of cells, stored row by row.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from its cells, row by row.
+    ///
+    /// # Panics
+    /// If the number of cells is not a multiple of `width`.
+    #[must_use]
+    pub fn from_vec(width: usize, cells: Vec<T>) -> Self {
+        let height = cells.len().checked_div(width).unwrap_or_default();
+        assert_eq!(
+            width * height,
+            cells.len(),
+            "expected {} cells to fill rows of width {width}.",
+            cells.len()
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Reads a grid from puzzle text with one row per line, converting every character with `cell`.
+    /// Blank lines at the start and the end of the input are ignored.
+    pub fn parse_with(
+        input: &str,
+        mut cell: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, GridError> {
+        let lines: Vec<(usize, &str)> = input
+            .lines()
+            .enumerate()
+            .skip_while(|(_, line)| line.trim().is_empty())
+            .collect();
+        let end = lines
+            .iter()
+            .rposition(|(_, line)| !line.trim().is_empty())
+            .map_or(0, |i| i + 1);
+
+        let mut cells = vec![];
+        let mut width = None;
+
+        for (index, line) in &lines[..end] {
+            let line_width = line.chars().count();
+            let expected = *width.get_or_insert(line_width);
+            if line_width != expected {
+                return Err(GridError::Ragged {
+                    line: index + 1,
+                    expected,
+                    found: line_width,
+                });
+            }
+
+            for (column, c) in line.chars().enumerate() {
+                cells.push(cell(c).ok_or(GridError::InvalidCell {
+                    line: index + 1,
+                    column: column + 1,
+                    cell: c,
+                })?);
+            }
+        }
+
+        Ok(Self {
+            width: width.unwrap_or_default(),
+            height: end,
+            cells,
+        })
+    }
+
+    #[must_use]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[must_use]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether a point lies on the grid.
+    #[must_use]
+    pub fn contains(&self, point: Point) -> bool {
+        usize::try_from(point.x).is_ok_and(|x| x < self.width)
+            && usize::try_from(point.y).is_ok_and(|y| y < self.height)
+    }
+
+    #[allow(clippy::cast_sign_loss, clippy::cast_possible_truncation)]
+    fn index_of(&self, point: Point) -> Option<usize> {
+        self.contains(point)
+            .then(|| point.y as usize * self.width + point.x as usize)
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn point_of(&self, index: usize) -> Point {
+        Point::new((index % self.width) as i64, (index / self.width) as i64)
+    }
+
+    /// The cell at a point, `None` if the point is off the grid.
+    #[must_use]
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.index_of(point).map(|i| &self.cells[i])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.index_of(point).map(|i| &mut self.cells[i])
+    }
+
+    /// All points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> + use<T> {
+        let width = self.width;
+        #[allow(clippy::cast_possible_wrap)]
+        (0..self.cells.len()).map(move |i| Point::new((i % width) as i64, (i / width) as i64))
+    }
+
+    /// All cells with their points, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| (self.point_of(i), cell))
+    }
+
+    /// The points next to `point` in the four directions that lie on the grid.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbors4().filter(|p| self.contains(*p))
+    }
+
+    /// The points around `point`, including diagonals, that lie on the grid.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        point.neighbors8().filter(|p| self.contains(*p))
+    }
+
+    /// The cells from `start` (inclusive) in steps of `delta`, until the edge of the grid.
+    ///
+    /// # Panics
+    /// If `delta` is the origin, which would never leave the grid.
+    pub fn ray(&self, start: Point, delta: Point) -> impl Iterator<Item = (Point, &T)> {
+        assert_ne!(delta, Point::ORIGIN, "expected a ray to move.");
+        std::iter::successors(Some(start), move |p| Some(*p + delta))
+            .map_while(|p| self.get(p).map(|cell| (p, cell)))
+    }
+
+    /// The cells of row `y`.
+    ///
+    /// # Panics
+    /// If `y` is not a row of the grid.
+    #[must_use]
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// All rows, top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // NOTE: `chunks` panics for a chunk size of 0.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of column `x`, top to bottom.
+    ///
+    /// # Panics
+    /// If `x` is not a column of the grid.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(
+            x < self.width,
+            "column {x} is off the grid of width {}.",
+            self.width
+        );
+        self.cells
+            .iter()
+            .skip(x)
+            .step_by(self.width.max(1))
+            .take(self.height)
+    }
+
+    /// All columns, left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All diagonals that run down and to the right, starting from the bottom-left corner.
+    pub fn diagonals(&self) -> impl Iterator<Item = Vec<&T>> {
+        self.diagonal_starts(true)
+            .map(|start| self.ray(start, Point::new(1, 1)).map(|(_, c)| c).collect())
+    }
+
+    /// All diagonals that run down and to the left, starting from the top-left corner.
+    pub fn anti_diagonals(&self) -> impl Iterator<Item = Vec<&T>> {
+        self.diagonal_starts(false)
+            .map(|start| self.ray(start, Point::new(-1, 1)).map(|(_, c)| c).collect())
+    }
+
+    /// The first cells of all diagonals: down the left or right edge, then along the top edge.
+    #[allow(clippy::cast_possible_wrap)]
+    fn diagonal_starts(&self, down_right: bool) -> impl Iterator<Item = Point> + use<T> {
+        let (width, height) = (self.width as i64, self.height as i64);
+        let (edge, top): (Vec<Point>, Vec<Point>) = if down_right {
+            (
+                (0..height).rev().map(|y| Point::new(0, y)).collect(),
+                (1..width).map(|x| Point::new(x, 0)).collect(),
+            )
+        } else {
+            (
+                (0..width).map(|x| Point::new(x, 0)).collect(),
+                (1..height).map(|y| Point::new(width - 1, y)).collect(),
+            )
+        };
+        edge.into_iter().chain(top)
+    }
+
+    /// The point of the first cell, row by row, that matches `predicate`.
+    pub fn position(&self, predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.cells
+            .iter()
+            .position(predicate)
+            .map(|i| self.point_of(i))
+    }
+
+    /// The points of all cells that match `predicate`, row by row.
+    pub fn positions(&self, mut predicate: impl FnMut(&T) -> bool) -> impl Iterator<Item = Point> {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// Converts every cell of the grid.
+    #[must_use]
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid with every cell set to `value`.
+    #[must_use]
+    pub fn new(width: usize, height: usize, value: T) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, so rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self {
+        self.rearrange(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// Rotates the grid by 90° clockwise.
+    #[must_use]
+    pub fn rotate_right(&self) -> Self {
+        let height = self.height;
+        self.rearrange(height, self.width, |x, y| (y, height - 1 - x))
+    }
+
+    /// Rotates the grid by 90° counter-clockwise.
+    #[must_use]
+    pub fn rotate_left(&self) -> Self {
+        let width = self.width;
+        self.rearrange(self.height, width, |x, y| (width - 1 - y, x))
+    }
+
+    /// Builds a grid of the given size whose cell `(x, y)` is the cell `source(x, y)` of this grid.
+    fn rearrange(
+        &self,
+        width: usize,
+        height: usize,
+        source: impl Fn(usize, usize) -> (usize, usize),
+    ) -> Self {
+        let mut cells = Vec::with_capacity(self.cells.len());
+        for y in 0..height {
+            for x in 0..width {
+                let (sx, sy) = source(x, y);
+                cells.push(self.cells[sy * self.width + sx].clone());
+            }
+        }
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// The point of the first cell, row by row, that equals `value`.
+    pub fn find(&self, value: &T) -> Option<Point> {
+        self.position(|cell| cell == value)
+    }
+}
+
+impl Grid<char> {
+    /// Reads a grid of characters, e.g. a map of `#` and `.`.
+    pub fn parse(input: &str) -> Result<Self, GridError> {
+        Self::parse_with(input, Some)
+    }
+}
+
+impl Grid<u8> {
+    /// Reads a grid of single digits, e.g. a height map.
+    pub fn parse_digits(input: &str) -> Result<Self, GridError> {
+        #[allow(clippy::cast_possible_truncation)]
+        Self::parse_with(input, |c| c.to_digit(10).map(|d| d as u8))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    /// # Panics
+    /// If the point is off the grid.
+    fn index(&self, point: Point) -> &T {
+        self.get(point)
+            .unwrap_or_else(|| panic!("point {point} is off the grid."))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    /// # Panics
+    /// If the point is off the grid.
+    fn index_mut(&mut self, point: Point) -> &mut T {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("point {point} is off the grid."))
+    }
+}
+
+/// Formats the grid like the puzzle input, one row per line, without a trailing newline.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Grid, GridError, Point};
+
+    const MAP: &str = "#.S\n..#\nE..\n";
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = Grid::parse(MAP).unwrap();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 3);
+        assert_eq!(grid[Point::new(2, 0)], 'S');
+        assert_eq!(grid.to_string(), MAP.trim_end());
+        assert_eq!(Grid::parse(&grid.to_string()).unwrap(), grid);
+
+        let empty = Grid::parse("\n\n").unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(empty.to_string(), "");
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(
+            Grid::parse("..\n...\n"),
+            Err(GridError::Ragged {
+                line: 2,
+                expected: 2,
+                found: 3
+            })
+        );
+        assert_eq!(
+            Grid::parse_digits("12\n3x"),
+            Err(GridError::InvalidCell {
+                line: 2,
+                column: 2,
+                cell: 'x'
+            })
+        );
+        assert_eq!(Grid::parse_digits("12\n34").unwrap()[Point::new(1, 1)], 4);
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let grid = Grid::parse(MAP).unwrap();
+        assert!(grid.contains(Point::new(2, 2)));
+        assert!(!grid.contains(Point::new(3, 0)));
+        assert!(!grid.contains(Point::new(0, -1)));
+        assert_eq!(grid.get(Point::new(-1, 0)), None);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = Grid::parse(MAP).unwrap();
+        assert_eq!(grid.find(&'S'), Some(Point::new(2, 0)));
+        assert_eq!(grid.find(&'E'), Some(Point::new(0, 2)));
+        assert_eq!(grid.find(&'X'), None);
+        assert_eq!(
+            grid.positions(|c| *c == '#').collect::<Vec<_>>(),
+            vec![Point::new(0, 0), Point::new(2, 1)]
+        );
+    }
+
+    #[test]
+    fn iterates_neighbors() {
+        let grid = Grid::parse(MAP).unwrap();
+        assert_eq!(
+            grid.neighbors4(Point::ORIGIN).collect::<Vec<_>>(),
+            vec![Point::new(1, 0), Point::new(0, 1)]
+        );
+        assert_eq!(grid.neighbors8(Point::new(1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8(Point::new(2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn views_rows_columns_and_diagonals() {
+        let grid = Grid::parse("abc\ndef").unwrap();
+        assert_eq!(grid.row(1), &['d', 'e', 'f']);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(
+            grid.columns()
+                .map(|c| c.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+
+        let diagonals: Vec<String> = grid.diagonals().map(|d| d.into_iter().collect()).collect();
+        assert_eq!(diagonals, vec!["d", "ae", "bf", "c"]);
+
+        let anti_diagonals: Vec<String> = grid
+            .anti_diagonals()
+            .map(|d| d.into_iter().collect())
+            .collect();
+        assert_eq!(anti_diagonals, vec!["a", "bd", "ce", "f"]);
+
+        let ray: String = grid
+            .ray(Point::new(2, 0), Direction::Left.delta())
+            .map(|(_, c)| c)
+            .collect();
+        assert_eq!(ray, "cba");
+    }
+
+    #[test]
+    #[should_panic(expected = "column 3 is off the grid of width 3.")]
+    fn rejects_columns_off_the_grid() {
+        let grid = Grid::parse("abc\ndef").unwrap();
+        let _ = grid.column(3);
+    }
+
+    #[test]
+    fn rotates_and_transposes() {
+        let grid = Grid::parse("abc\ndef").unwrap();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_right().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_left().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_right().rotate_left(), grid);
+        assert_eq!(grid.rotate_right().rotate_right().to_string(), "fed\ncba");
+    }
+
+    #[test]
+    fn moves_points() {
+        let mut point = Point::new(1, 1);
+        point += Direction::Up;
+        assert_eq!(point, Point::new(1, 0));
+        assert_eq!(point + Direction::Right.delta() * 3, Point::new(4, 0));
+        assert_eq!(Point::ORIGIN.manhattan(Point::new(-3, 4)), 7);
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        assert_eq!(Direction::try_from('v'), Ok(Direction::Down));
+        assert_eq!(
+            Direction::try_from('x'),
+            Err(GridError::InvalidDirection('x'))
+        );
+    }
+
+    #[test]
+    fn maps_and_mutates_cells() {
+        let mut grid = Grid::new(2, 2, 0_u32);
+        grid[Point::new(1, 0)] = 5;
+        *grid.get_mut(Point::new(0, 1)).unwrap() += 2;
+        assert_eq!(grid.to_string(), "05\n20");
+        assert_eq!(grid.map(|c| c * 2).to_string(), "010\n40");
+        assert_eq!(Grid::from_vec(2, vec![1, 2, 3, 4]).to_string(), "12\n34");
+    }
+}
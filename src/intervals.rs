@@ -0,0 +1,466 @@
+//! Sets of integers that are stored as ranges, for puzzles that map huge numeric ranges like seeds or IDs.
+//!
+//! A `RangeSet` keeps its values as sorted, disjoint half-open ranges in a `Vec`, so lookups are binary searches
+//! and set operations are a single pass over both sets. Ranges that overlap or touch are merged.
+
+use std::ops::{Range, RangeInclusive};
+
+use crate::math::Integer;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    /// Non-empty ranges in ascending order, with a gap between each pair of neighbors.
+    ranges: Vec<Range<T>>,
+}
+
+impl<T: Integer> RangeSet<T> {
+    /// Creates an empty set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self { ranges: vec![] }
+    }
+
+    /// The disjoint ranges of the set in ascending order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    /// The number of values in the set. Overflows if the count doesn't fit `T`.
+    pub fn len(&self) -> T {
+        self.ranges
+            .iter()
+            .fold(T::ZERO, |count, range| count + (range.end - range.start))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        let index = self.ranges.partition_point(|range| range.end <= value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.start <= value)
+    }
+
+    /// The smallest value of the set, e.g. the lowest location of all seeds.
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    /// The largest value of the set.
+    pub fn max(&self) -> Option<T> {
+        self.ranges.last().map(|range| range.end - T::ONE)
+    }
+
+    /// Adds the values of `range`, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = if first < last {
+            self.ranges[first].start.min(range.start)..self.ranges[last - 1].end.max(range.end)
+        } else {
+            range
+        };
+
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    /// Adds the values of an inclusive range like `3..=5`, as puzzles usually write them.
+    /// Panics if the range ends at the largest value of `T`.
+    pub fn insert_inclusive(&mut self, range: RangeInclusive<T>) {
+        let (start, end) = range.into_inner();
+        let end = end
+            .checked_add(T::ONE)
+            .expect("inclusive range must end below the largest value of its type.");
+        self.insert(start..end);
+    }
+
+    /// Removes the values of `range`, splitting the ranges it cuts through.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        let first = self.ranges.partition_point(|r| r.end <= range.start);
+        let last = self.ranges.partition_point(|r| r.start < range.end);
+        if first == last {
+            return;
+        }
+
+        let mut remaining = vec![];
+        if self.ranges[first].start < range.start {
+            remaining.push(self.ranges[first].start..range.start);
+        }
+        if self.ranges[last - 1].end > range.end {
+            remaining.push(range.end..self.ranges[last - 1].end);
+        }
+
+        self.ranges.splice(first..last, remaining);
+    }
+
+    /// The values that are in either set.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result: Vec<Range<T>> = Vec::with_capacity(self.ranges.len() + other.ranges.len());
+        let (mut a, mut b) = (
+            self.ranges.iter().peekable(),
+            other.ranges.iter().peekable(),
+        );
+
+        loop {
+            let next = match (a.peek(), b.peek()) {
+                (Some(x), Some(y)) if x.start <= y.start => a.next(),
+                (Some(_), Some(_)) => b.next(),
+                (Some(_), None) => a.next(),
+                (None, _) => b.next(),
+            };
+            let Some(next) = next else {
+                break;
+            };
+
+            match result.last_mut() {
+                Some(last) if next.start <= last.end => last.end = last.end.max(next.end),
+                _ => result.push(next.clone()),
+            }
+        }
+
+        Self { ranges: result }
+    }
+
+    /// The values that are in both sets.
+    #[must_use]
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (&self.ranges[i], &other.ranges[j]);
+            let start = a.start.max(b.start);
+            let end = a.end.min(b.end);
+            if start < end {
+                result.push(start..end);
+            }
+
+            if a.end <= b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges: result }
+    }
+
+    /// The values of this set that are not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = vec![];
+        let mut j = 0;
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            // NOTE: ranges of `other` that end before this range can't affect later ranges either.
+            while j < other.ranges.len() && other.ranges[j].end <= start {
+                j += 1;
+            }
+
+            let mut k = j;
+            while k < other.ranges.len() && other.ranges[k].start < range.end {
+                let cut = &other.ranges[k];
+                if cut.start > start {
+                    result.push(start..cut.start);
+                }
+                start = start.max(cut.end);
+                k += 1;
+            }
+
+            if start < range.end {
+                result.push(start..range.end);
+            }
+        }
+
+        Self { ranges: result }
+    }
+
+    /// Splits the set into the values below `value` and the values from `value` on.
+    #[must_use]
+    pub fn split_at(&self, value: T) -> (Self, Self) {
+        let index = self.ranges.partition_point(|range| range.end <= value);
+        let mut below = self.ranges[..index].to_vec();
+        let mut above = self.ranges[index..].to_vec();
+
+        if let Some(range) = above.first_mut()
+            && range.start < value
+        {
+            below.push(range.start..value);
+            range.start = value;
+        }
+
+        (Self { ranges: below }, Self { ranges: above })
+    }
+
+    /// Moves the values of each source range to the range of the same length that starts at its destination, like
+    /// the maps of seeds to soil. Values outside of all sources keep their value. If sources overlap, the first
+    /// mapping that contains a value moves it.
+    /// Panics if a moved value doesn't fit `T`, e.g. when `250..255` of `u8` is moved to start at `253`.
+    #[must_use]
+    pub fn remap(&self, mappings: impl IntoIterator<Item = (Range<T>, T)>) -> Self {
+        let mut remaining = self.clone();
+        let mut moved = Self::new();
+
+        for (source, destination) in mappings {
+            let source_start = source.start;
+            let source = Self::from(source);
+            for range in remaining.intersection(&source).ranges {
+                let shift = |value: T| {
+                    offset_by(destination, distance(source_start, value))
+                        .unwrap_or_else(|| {
+                            panic!(
+                                "cannot move {value} from a range starting at {source_start} to one starting at {destination}, it doesn't fit its type."
+                            )
+                        })
+                };
+                moved.insert(shift(range.start)..shift(range.end));
+            }
+            remaining = remaining.difference(&source);
+        }
+
+        remaining.union(&moved)
+    }
+}
+
+/// The distance from `from` up to `to`, which can exceed `T` for signed types, e.g. from `-100_i8` to `100`.
+fn distance<T: Integer>(from: T, to: T) -> u128 {
+    if from >= T::ZERO {
+        to.to_u128() - from.to_u128()
+    } else if to < T::ZERO {
+        from.unsigned_abs() - to.unsigned_abs()
+    } else {
+        from.unsigned_abs() + to.to_u128()
+    }
+}
+
+/// `base + offset`, `None` if it doesn't fit `T`.
+fn offset_by<T: Integer>(base: T, offset: u128) -> Option<T> {
+    if base >= T::ZERO {
+        return T::try_from_u128(base.to_u128().checked_add(offset)?);
+    }
+
+    let magnitude = base.unsigned_abs();
+    if offset >= magnitude {
+        T::try_from_u128(offset - magnitude)
+    } else {
+        // NOTE: the result is negative, `-(magnitude - offset)`, and its magnitude can be one more than `T::MAX`.
+        Some(T::ZERO - T::try_from_u128(magnitude - offset - 1)? - T::ONE)
+    }
+}
+
+impl<T: Integer> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        let mut set = Self::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl<T: Integer> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+impl<T: Integer> FromIterator<RangeInclusive<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        for range in iter {
+            set.insert_inclusive(range);
+        }
+        set
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::ops::Range;
+
+    use super::RangeSet;
+    use crate::rng::Rng;
+
+    /// Values of the small domain that the properties are checked on.
+    const DOMAIN: Range<i32> = -20..60;
+
+    fn random_range(rng: &mut Rng) -> Range<i32> {
+        let start = rng.range(DOMAIN.start.into(), (DOMAIN.end - 1).into()) as i32;
+        start..start + rng.below(15) as i32
+    }
+
+    fn random_ranges(rng: &mut Rng) -> Vec<Range<i32>> {
+        (0..rng.below(6)).map(|_| random_range(rng)).collect()
+    }
+
+    fn values(set: &RangeSet<i32>) -> BTreeSet<i32> {
+        set.ranges().iter().flat_map(Clone::clone).collect()
+    }
+
+    /// The ranges must stay sorted, non-empty and separated by gaps.
+    fn assert_normalized(set: &RangeSet<i32>) {
+        assert!(set.ranges().iter().all(|range| range.start < range.end));
+        assert!(set.ranges().windows(2).all(|w| w[0].end < w[1].start));
+    }
+
+    #[test]
+    fn merges_and_splits_ranges() {
+        let mut set = RangeSet::new();
+        set.insert(10..20);
+        set.insert(30..40);
+        set.insert(20..25);
+        assert_eq!(set.ranges(), &[10..25, 30..40]);
+
+        set.insert(5..35);
+        assert_eq!(set.ranges(), &[5..40]);
+
+        set.remove(10..12);
+        set.remove(38..50);
+        assert_eq!(set.ranges(), &[5..10, 12..38]);
+        assert_eq!(set.len(), 31);
+        assert_eq!((set.min(), set.max()), (Some(5), Some(37)));
+
+        assert!(set.contains(5) && set.contains(37));
+        assert!(!set.contains(10) && !set.contains(38) && !set.contains(4));
+
+        set.insert(7..7);
+        set.remove(0..100);
+        assert!(set.is_empty());
+        assert_eq!(set.min(), None);
+    }
+
+    #[test]
+    fn reads_inclusive_ranges() {
+        let set: RangeSet<u64> = [3..=5, 10..=14, 16..=20, 12..=18].into_iter().collect();
+        assert_eq!(set.ranges(), &[3..6, 10..21]);
+        assert_eq!(set.len(), 14);
+
+        let mut set = RangeSet::new();
+        set.insert_inclusive(0_u8..=254);
+        assert_eq!(set.len(), 255);
+    }
+
+    #[test]
+    fn combines_sets() {
+        let a: RangeSet<u32> = [0..10, 20..30].into_iter().collect();
+        let b: RangeSet<u32> = [5..25, 40..50].into_iter().collect();
+
+        assert_eq!(a.union(&b).ranges(), &[0..30, 40..50]);
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..30]);
+        assert_eq!(b.difference(&a).ranges(), &[10..20, 40..50]);
+
+        let (below, above) = a.split_at(25);
+        assert_eq!(below.ranges(), &[0..10, 20..25]);
+        assert_eq!(above.ranges(), &[25..30]);
+
+        let (below, above) = a.split_at(15);
+        assert_eq!(below.ranges(), &[0..10]);
+        assert_eq!(above.ranges(), &[20..30]);
+    }
+
+    #[test]
+    fn remaps_ranges() {
+        // the seed-to-soil map of the example: 50 98 2, 52 50 48.
+        let seeds: RangeSet<u64> = [79..93, 55..68].into_iter().collect();
+        let soil = seeds.remap([(98..100, 50), (50..98, 52)]);
+        assert_eq!(soil.ranges(), &[57..70, 81..95]);
+
+        let set = RangeSet::from(0_u64..10);
+        let moved = set.remap([(2..4, 100), (3..6, 200)]);
+        assert_eq!(moved.ranges(), &[0..2, 6..10, 100..102, 201..203]);
+
+        let set = RangeSet::from(250_u8..252);
+        assert_eq!(set.remap([(250..255, 253)]).ranges(), &[253..255]);
+
+        let set = RangeSet::from(-100_i8..100);
+        assert_eq!(set.remap([(-100..100, -128)]).ranges(), &[-128..72]);
+        assert_eq!(
+            set.remap([(-100..-90, 117)]).ranges(),
+            &[-90..100, 117..127]
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "cannot move 255 from a range starting at 250 to one starting at 253"
+    )]
+    fn rejects_remapping_past_the_type() {
+        let _ = RangeSet::from(250_u8..255).remap([(250..255, 253)]);
+    }
+
+    #[test]
+    fn matches_sets_of_values() {
+        let mut rng = Rng::new(0x853c_49e6_748f_ea9b);
+
+        for _ in 0..1000 {
+            let (ranges_a, ranges_b) = (random_ranges(&mut rng), random_ranges(&mut rng));
+            let a: RangeSet<i32> = ranges_a.iter().cloned().collect();
+            let b: RangeSet<i32> = ranges_b.iter().cloned().collect();
+
+            let (va, vb) = (values(&a), values(&b));
+            assert_normalized(&a);
+            assert_eq!(va, ranges_a.into_iter().flatten().collect());
+            assert_eq!(vb, ranges_b.into_iter().flatten().collect());
+
+            for set in [a.union(&b), a.intersection(&b), a.difference(&b)] {
+                assert_normalized(&set);
+            }
+            assert_eq!(values(&a.union(&b)), &va | &vb);
+            assert_eq!(values(&a.intersection(&b)), &va & &vb);
+            assert_eq!(values(&a.difference(&b)), &va - &vb);
+            assert_eq!(a.len() as usize, va.len());
+
+            let mut removed = a.clone();
+            let range = random_range(&mut rng);
+            removed.remove(range.clone());
+            assert_normalized(&removed);
+            assert_eq!(values(&removed), &va - &range.clone().collect());
+
+            let split = DOMAIN.start + rng.below(DOMAIN.len() as u64) as i32;
+            let (below, above) = a.split_at(split);
+            assert!(values(&below).iter().all(|v| *v < split));
+            assert!(values(&above).iter().all(|v| *v >= split));
+            assert_eq!(&values(&below) | &values(&above), va);
+
+            for value in DOMAIN {
+                assert_eq!(a.contains(value), va.contains(&value));
+            }
+
+            let source = random_range(&mut rng);
+            let destination = random_range(&mut rng).start;
+            let remapped = a.remap([(source.clone(), destination)]);
+            let expected: BTreeSet<i32> = va
+                .iter()
+                .map(|v| {
+                    if source.contains(v) {
+                        destination + (v - source.start)
+                    } else {
+                        *v
+                    }
+                })
+                .collect();
+            assert_normalized(&remapped);
+            assert_eq!(values(&remapped), expected);
+        }
+    }
+}
@@ -1,3 +1,11 @@
+pub mod grid;
+pub mod intervals;
+pub mod math;
+pub mod parse;
+pub mod search;
 pub mod template;
 
+#[cfg(feature = "test_lib")]
+mod rng;
+
 // Use this file to add helper functions and additional modules.
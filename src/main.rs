@@ -1,4 +1,6 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    download, examples, profile, read, scaffold, solve, watch,
+};
 use args::{AppArguments, parse};
 
 #[cfg(feature = "today")]
@@ -7,8 +9,10 @@ use advent_of_code::template::Day;
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::runner::OutputFormat;
+    use advent_of_code::template::{Day, Year};
     use std::process;
+    use std::time::Duration;
 
     pub enum AppArguments {
         Download {
@@ -17,62 +21,108 @@ mod args {
         Read {
             day: Day,
         },
+        Examples {
+            day: Day,
+            overwrite: bool,
+        },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            template: Option<String>,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
+            heap: bool,
             submit: Option<u8>,
+            format: OutputFormat,
+            examples: bool,
         },
-        All {
-            release: bool,
+        ProfileCpu {
+            day: Day,
+            part: u8,
+            duration: Duration,
         },
-        Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
+        WatchDay {
+            day: Day,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    fn free_day(
+        args: &mut pico_args::Arguments,
+        year: Year,
+    ) -> Result<Day, Box<dyn std::error::Error>> {
+        let day: String = args.free_from_str()?;
+        Ok(Day::from_str_in_year(&day, year)?)
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+        let subcommand = args.subcommand()?;
 
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
+        // NOTE: options need to be consumed before free arguments.
+        let year: Year = args
+            .opt_value_from_str("--year")?
+            .unwrap_or_else(Year::configured);
+
+        let app_args = match subcommand.as_deref() {
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                day: free_day(&mut args, year)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                day: free_day(&mut args, year)?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
+            Some("examples") => AppArguments::Examples {
+                day: free_day(&mut args, year)?,
                 overwrite: args.contains("--overwrite"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("scaffold") => {
+                let template = args.opt_value_from_str("--template")?;
+
+                AppArguments::Scaffold {
+                    day: free_day(&mut args, year)?,
+                    download: args.contains("--download"),
+                    overwrite: args.contains("--overwrite"),
+                    template,
+                }
+            }
+            Some("solve") if args.contains("--profile-cpu") => {
+                let part = args.opt_value_from_str("--part")?.unwrap_or(1);
+                let seconds: f64 = args.opt_value_from_str("--seconds")?.unwrap_or(10.0);
+
+                if !(1..=2).contains(&part) || seconds <= 0.0 {
+                    eprintln!(
+                        "Unexpected command-line input. Format: cargo solve 1 --profile-cpu --part 1 --seconds 10"
+                    );
+                    process::exit(1);
+                }
+
+                AppArguments::ProfileCpu {
+                    day: free_day(&mut args, year)?,
+                    part,
+                    duration: Duration::from_secs_f64(seconds),
+                }
+            }
+            Some("solve") => {
+                let format = args.opt_value_from_str("--format")?.unwrap_or_default();
+
+                AppArguments::Solve {
+                    day: free_day(&mut args, year)?,
+                    release: args.contains("--release"),
+                    submit: args.opt_value_from_str("--submit")?,
+                    dhat: args.contains("--dhat"),
+                    heap: args.contains("--heap"),
+                    format,
+                    examples: args.contains("--examples"),
+                }
+            }
+            Some("watch-day") => AppArguments::WatchDay {
+                day: free_day(&mut args, year)?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
@@ -102,38 +152,48 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
+            AppArguments::Examples { day, overwrite } => examples::handle(day, overwrite),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                // NOTE: download first, so the scaffold can pick up the examples of the puzzle description.
                 if download {
                     download::handle(day);
                 }
+                scaffold::handle(day, overwrite, template.as_deref());
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                heap,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                format,
+                examples,
+            } => solve::handle(day, release, dhat, heap, submit, format, examples),
+            AppArguments::ProfileCpu {
+                day,
+                part,
+                duration,
+            } => profile::handle(day, part, duration),
+            AppArguments::WatchDay { day } => watch::handle(day),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
                         download::handle(day);
+                        scaffold::handle(day, false, None);
                         read::handle(day)
                     }
                     None => {
                         eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
+                            "`today` command can only be run on a day of advent in \
+                            december. Please use `scaffold` with a specific day."
                         );
                         process::exit(1)
                     }
@@ -0,0 +1,602 @@
+//! Number theory and combinatorics that puzzles keep coming back to: greatest common divisors and least
+//! common multiples of cycle lengths, modular arithmetic, the chinese remainder theorem, integer square roots,
+//! binomial coefficients and extrapolating polynomial sequences.
+//!
+//! Functions are generic over the primitive integer types. Results that can overflow return `None` instead, except
+//! for `gcd`, which only overflows for `MIN` of signed types and panics. Modular products are computed without
+//! overflow for moduli up to `i128::MAX` and `u128::MAX`.
+
+use std::fmt::{Debug, Display};
+use std::ops::{Add, Div, Mul, Neg, Rem, Sub};
+
+/// A primitive integer type.
+pub trait Integer:
+    Copy
+    + Ord
+    + Debug
+    + Display
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+    fn checked_sub(self, other: Self) -> Option<Self>;
+    fn checked_mul(self, other: Self) -> Option<Self>;
+    /// The remainder that is never negative, i.e. `self` modulo `modulus`.
+    fn rem_euclid(self, modulus: Self) -> Self;
+    /// The square root, rounded down. Panics for negative values.
+    fn isqrt(self) -> Self;
+    /// Converts a value that is not negative.
+    fn to_u128(self) -> u128;
+    /// Converts a value that fits the type.
+    fn from_u128(value: u128) -> Self;
+
+    /// The absolute value as a `u128`, which never overflows, even for `MIN` of a signed type.
+    fn unsigned_abs(self) -> u128 {
+        if self < Self::ZERO {
+            // NOTE: `-(self + 1)` fits the type, `-self` doesn't for `MIN`.
+            (Self::ZERO - (self + Self::ONE)).to_u128() + 1
+        } else {
+            self.to_u128()
+        }
+    }
+
+    /// Converts a `u128`, `None` if it doesn't fit the type.
+    fn try_from_u128(value: u128) -> Option<Self> {
+        let converted = Self::from_u128(value);
+        (converted >= Self::ZERO && converted.to_u128() == value).then_some(converted)
+    }
+}
+
+/// A primitive integer type that can be negative.
+pub trait Signed: Integer + Neg<Output = Self> {}
+
+macro_rules! impl_integer {
+    ($($t:ty),*) => {
+        $(impl Integer for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, other: Self) -> Option<Self> {
+                <$t>::checked_add(self, other)
+            }
+
+            fn checked_sub(self, other: Self) -> Option<Self> {
+                <$t>::checked_sub(self, other)
+            }
+
+            fn checked_mul(self, other: Self) -> Option<Self> {
+                <$t>::checked_mul(self, other)
+            }
+
+            fn rem_euclid(self, modulus: Self) -> Self {
+                <$t>::rem_euclid(self, modulus)
+            }
+
+            fn isqrt(self) -> Self {
+                <$t>::isqrt(self)
+            }
+
+            fn to_u128(self) -> u128 {
+                self as u128
+            }
+
+            fn from_u128(value: u128) -> Self {
+                value as Self
+            }
+        })*
+    };
+}
+
+impl_integer!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize
+);
+
+macro_rules! impl_signed {
+    ($($t:ty),*) => {
+        $(impl Signed for $t {})*
+    };
+}
+
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+/* -------------------------------------------------------------------------- */
+
+fn gcd_u128(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The greatest common divisor, never negative. `gcd(0, 0)` is 0.
+///
+/// # Panics
+/// If the result doesn't fit `T`, which only happens for `gcd(T::MIN, 0)` and `gcd(T::MIN, T::MIN)` of signed types.
+pub fn gcd<T: Integer>(a: T, b: T) -> T {
+    let g = gcd_u128(a.unsigned_abs(), b.unsigned_abs());
+    T::try_from_u128(g).unwrap_or_else(|| panic!("gcd({a}, {b}) doesn't fit its type."))
+}
+
+/// The least common multiple, never negative. `None` if it overflows.
+pub fn lcm<T: Integer>(a: T, b: T) -> Option<T> {
+    let (a, b) = (a.unsigned_abs(), b.unsigned_abs());
+    if a == 0 || b == 0 {
+        return Some(T::ZERO);
+    }
+    T::try_from_u128((a / gcd_u128(a, b)).checked_mul(b)?)
+}
+
+/// The least common multiple of all values, e.g. the step at which cycles of different lengths line up.
+/// `None` if it overflows, 1 if there are no values.
+pub fn lcm_all<T: Integer>(values: impl IntoIterator<Item = T>) -> Option<T> {
+    values
+        .into_iter()
+        .try_fold(T::ONE, |result, value| lcm(result, value))
+}
+
+/// The greatest common divisor `g` of `a` and `b` together with the coefficients `x` and `y` of
+/// `a * x + b * y = g` (Bézout's identity).
+pub fn extended_gcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (T::ONE, T::ZERO);
+    let (mut old_y, mut y) = (T::ZERO, T::ONE);
+
+    while r != T::ZERO {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < T::ZERO {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn add_mod_u128(a: u128, b: u128, modulus: u128) -> u128 {
+    // NOTE: `a + b` can overflow for moduli above `u128::MAX / 2`.
+    if a >= modulus - b {
+        a - (modulus - b)
+    } else {
+        a + b
+    }
+}
+
+fn mul_mod_u128(a: u128, b: u128, modulus: u128) -> u128 {
+    if let Some(product) = a.checked_mul(b) {
+        return product % modulus;
+    }
+
+    let (mut a, mut b) = (a % modulus, b % modulus);
+    let mut result = 0;
+    while b > 0 {
+        if b & 1 == 1 {
+            result = add_mod_u128(result, a, modulus);
+        }
+        a = add_mod_u128(a, a, modulus);
+        b >>= 1;
+    }
+    result
+}
+
+fn assert_modulus<T: Integer>(modulus: T) {
+    assert!(
+        modulus > T::ZERO,
+        "modulus must be positive, was {modulus}."
+    );
+}
+
+/// `(a + b) mod modulus`, without overflow. Panics if `modulus` isn't positive.
+pub fn mod_add<T: Integer>(a: T, b: T, modulus: T) -> T {
+    assert_modulus(modulus);
+    T::from_u128(add_mod_u128(
+        a.rem_euclid(modulus).to_u128(),
+        b.rem_euclid(modulus).to_u128(),
+        modulus.to_u128(),
+    ))
+}
+
+/// `(a * b) mod modulus`, without overflow. Panics if `modulus` isn't positive.
+pub fn mod_mul<T: Integer>(a: T, b: T, modulus: T) -> T {
+    assert_modulus(modulus);
+    T::from_u128(mul_mod_u128(
+        a.rem_euclid(modulus).to_u128(),
+        b.rem_euclid(modulus).to_u128(),
+        modulus.to_u128(),
+    ))
+}
+
+/// `base ^ exp mod modulus` by repeated squaring, without overflow.
+/// Panics if `exp` is negative or `modulus` isn't positive.
+pub fn mod_pow<T: Integer>(base: T, exp: T, modulus: T) -> T {
+    assert_modulus(modulus);
+    assert!(exp >= T::ZERO, "exponent must not be negative, was {exp}.");
+
+    let mut base = base.rem_euclid(modulus).to_u128();
+    let modulus = modulus.to_u128();
+    let mut exp = exp.to_u128();
+    let mut result = 1 % modulus;
+
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = mul_mod_u128(result, base, modulus);
+        }
+        base = mul_mod_u128(base, base, modulus);
+        exp >>= 1;
+    }
+
+    T::from_u128(result)
+}
+
+/// The `x` in `0..modulus` with `a * x mod modulus = 1`, `None` if `a` and `modulus` aren't coprime.
+/// Panics if `modulus` isn't positive.
+pub fn mod_inverse<T: Signed>(a: T, modulus: T) -> Option<T> {
+    assert_modulus(modulus);
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == T::ONE).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves a system of congruences `x mod n = a`, given as pairs `(a, n)`, with the chinese remainder theorem.
+/// The moduli don't need to be coprime.
+///
+/// Returns the smallest solution `x` that isn't negative together with the modulus of all solutions, the least common
+/// multiple of the moduli. `None` if the congruences contradict each other, a modulus isn't positive or the
+/// least common multiple overflows.
+pub fn crt<T: Signed>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    let (mut x, mut modulus) = (T::ZERO, T::ONE);
+
+    for (a, n) in congruences {
+        if n <= T::ZERO {
+            return None;
+        }
+
+        let a = a.rem_euclid(n);
+        let (g, p, _) = extended_gcd(modulus, n);
+        let difference = a - x;
+        if difference % g != T::ZERO {
+            return None;
+        }
+
+        // NOTE: `x + modulus * t` solves both congruences for `t = (a - x) / g * p mod (n / g)`.
+        let n_g = n / g;
+        let combined = modulus.checked_mul(n_g)?;
+        let t = mod_mul(difference / g, p, n_g);
+        x = mod_add(x, modulus * t, combined);
+        modulus = combined;
+    }
+
+    Some((x, modulus))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The square root, rounded down. Panics for negative values.
+pub fn isqrt<T: Integer>(n: T) -> T {
+    n.isqrt()
+}
+
+/// The generalized binomial coefficient `n choose k`, i.e. `n * (n - 1) * ... * (n - k + 1) / k!`,
+/// which is also defined for negative `n`. 0 for negative `k`, `None` if it overflows.
+pub fn binomial<T: Integer>(n: T, mut k: T) -> Option<T> {
+    if k < T::ZERO || (n >= T::ZERO && k > n) {
+        return Some(T::ZERO);
+    }
+    // NOTE: `n choose k = n choose (n - k)`, and fewer factors keep intermediate values small.
+    if n >= T::ZERO && k > n - k {
+        k = n - k;
+    }
+
+    let mut result = T::ONE;
+    let mut i = T::ZERO;
+
+    while i < k {
+        if n == i {
+            return Some(T::ZERO);
+        }
+
+        // NOTE: `result * (n - i)` is divisible by `i + 1`. Dividing first keeps intermediate values small.
+        let divisor = i + T::ONE;
+        let g = gcd(result, divisor);
+        let factor = (n.checked_sub(i)?) / (divisor / g);
+        result = (result / g).checked_mul(factor)?;
+        i = divisor;
+    }
+
+    Some(result)
+}
+
+/// The value at `x` of the polynomial of the lowest degree that goes through `values`, taken at `0, 1, 2, ...`.
+/// Extrapolates sequences like the growth of a pattern after 26501365 steps.
+///
+/// Uses Newton's forward differences, so everything stays an integer. `None` if there are no values or it overflows.
+pub fn extrapolate<T: Signed>(values: &[T], x: T) -> Option<T> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut row = values.to_vec();
+    let mut differences = vec![];
+    while row.iter().any(|value| *value != T::ZERO) {
+        differences.push(row[0]);
+        row = row
+            .windows(2)
+            .map(|pair| pair[1].checked_sub(pair[0]))
+            .collect::<Option<_>>()?;
+    }
+
+    let mut result = T::ZERO;
+    let mut k = T::ZERO;
+    for difference in differences {
+        let term = binomial(x, k)?.checked_mul(difference)?;
+        result = result.checked_add(term)?;
+        k = k + T::ONE;
+    }
+
+    Some(result)
+}
+
+/// The value at `x` of the polynomial of the lowest degree that goes through `points` (Lagrange interpolation).
+///
+/// `None` if the value isn't an integer, two points share an `x` or it overflows.
+pub fn lagrange<T: Signed>(points: &[(T, T)], x: T) -> Option<T> {
+    let mut xs: Vec<T> = points.iter().map(|(px, _)| *px).collect();
+    xs.sort_unstable();
+    if xs.windows(2).any(|pair| pair[0] == pair[1]) {
+        return None;
+    }
+
+    if let Some((_, y)) = points.iter().find(|(px, _)| *px == x) {
+        return Some(*y);
+    }
+
+    let (mut numerator, mut denominator) = (T::ZERO, T::ONE);
+
+    for (i, (xi, yi)) in points.iter().enumerate() {
+        let (mut term_numerator, mut term_denominator) = (*yi, T::ONE);
+
+        for (j, (xj, _)) in points.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            term_numerator = term_numerator.checked_mul(x.checked_sub(*xj)?)?;
+            term_denominator = term_denominator.checked_mul(xi.checked_sub(*xj)?)?;
+
+            let g = gcd(term_numerator, term_denominator);
+            term_numerator = term_numerator / g;
+            term_denominator = term_denominator / g;
+        }
+
+        // NOTE: a / b + c / d = (a * (d / g) + c * (b / g)) / (b / g * d) with g = gcd(b, d), to keep values small.
+        let g = gcd(denominator, term_denominator);
+        numerator = numerator
+            .checked_mul(term_denominator / g)?
+            .checked_add(term_numerator.checked_mul(denominator / g)?)?;
+        denominator = (denominator / g).checked_mul(term_denominator)?;
+
+        let g = gcd(numerator, denominator);
+        numerator = numerator / g;
+        denominator = denominator / g;
+    }
+
+    (numerator % denominator == T::ZERO).then(|| numerator / denominator)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        binomial, crt, extended_gcd, extrapolate, gcd, isqrt, lagrange, lcm, lcm_all, mod_add,
+        mod_inverse, mod_mul, mod_pow,
+    };
+    use crate::rng::Rng;
+
+    const CASES: usize = 2000;
+
+    #[test]
+    fn gcd_and_lcm_divide() {
+        let mut rng = Rng::new(0x2545_f491_4f6c_dd1d);
+
+        for _ in 0..CASES {
+            let a = rng.range(-1_000_000, 1_000_000);
+            let b = rng.range(-1_000_000, 1_000_000);
+
+            let g = gcd(a, b);
+            assert!(g >= 0);
+            if g != 0 {
+                assert_eq!((a % g, b % g), (0, 0));
+                assert_eq!(gcd(a / g, b / g), 1);
+            }
+
+            let l = lcm(a, b).unwrap();
+            assert!(l >= 0);
+            if l != 0 {
+                assert_eq!((l % a, l % b), (0, 0));
+                assert_eq!(i128::from(l) * i128::from(g), i128::from(a.abs() * b.abs()));
+            }
+
+            let (g2, x, y) = extended_gcd(a, b);
+            assert_eq!(g2, g);
+            assert_eq!(
+                i128::from(a) * i128::from(x) + i128::from(b) * i128::from(y),
+                i128::from(g)
+            );
+        }
+
+        assert_eq!(gcd(0_u32, 0), 0);
+        assert_eq!(lcm_all([2_u64, 3, 4, 5]), Some(60));
+        assert_eq!(lcm_all(Vec::<u8>::new()), Some(1));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(lcm(u128::MAX, 1), Some(u128::MAX));
+    }
+
+    #[test]
+    fn gcd_and_lcm_handle_min() {
+        assert_eq!(gcd(i64::MIN, 6), 2);
+        assert_eq!(gcd(i8::MIN, i8::MIN + 1), 1);
+        assert_eq!(lcm(i64::MIN, 3), None);
+        assert_eq!(lcm(i64::MIN, 0), Some(0));
+        assert_eq!(lcm(i64::MIN / 2, -2), Some(1 << 62));
+    }
+
+    #[test]
+    #[should_panic(expected = "gcd(-9223372036854775808, 0) doesn't fit its type.")]
+    fn gcd_panics_if_the_result_does_not_fit() {
+        let _ = gcd(i64::MIN, 0);
+    }
+
+    #[test]
+    fn modular_arithmetic_matches_wide_arithmetic() {
+        let mut rng = Rng::new(0x9e37_79b9_7f4a_7c15);
+
+        for _ in 0..CASES {
+            let a = rng.range(i64::MIN / 2, i64::MAX / 2);
+            let b = rng.range(i64::MIN / 2, i64::MAX / 2);
+            let m = rng.range(1, i64::MAX / 2);
+
+            let wide = |value: i128| value.rem_euclid(i128::from(m)) as i64;
+            assert_eq!(mod_mul(a, b, m), wide(i128::from(a) * i128::from(b)));
+            assert_eq!(mod_add(a, b, m), wide(i128::from(a) + i128::from(b)));
+
+            match mod_inverse(a, m) {
+                Some(inverse) => {
+                    assert!((0..m).contains(&inverse));
+                    assert_eq!(mod_mul(a, inverse, m), 1 % m);
+                }
+                None => assert_ne!(gcd(a, m), 1),
+            }
+        }
+
+        assert_eq!(mod_pow(3_u32, 200, 1_000_000_007), 136_318_165);
+        assert_eq!(mod_pow(-2_i32, 3, 5), 2);
+        assert_eq!(mod_pow(7_u8, 0, 1), 0);
+    }
+
+    #[test]
+    fn modular_arithmetic_does_not_overflow_i128() {
+        let mut rng = Rng::new(0xd1b5_4a32_d192_ed03);
+        // NOTE: 2^127 - 1 is prime, so Fermat's little theorem holds for every base.
+        let prime = i128::MAX;
+
+        for _ in 0..200 {
+            let a = ((i128::from(rng.next_u64()) << 64) | i128::from(rng.next_u64())) & i128::MAX;
+            if a == 0 {
+                continue;
+            }
+            assert_eq!(mod_pow(a, prime - 1, prime), 1);
+
+            let inverse = mod_inverse(a, prime).unwrap();
+            assert_eq!(mod_mul(a, inverse, prime), 1);
+            assert_eq!(mod_add(a, prime - 1, prime), a - 1);
+        }
+
+        assert_eq!(mod_mul(u128::MAX - 1, u128::MAX - 1, u128::MAX), 1);
+    }
+
+    #[test]
+    fn crt_solves_congruences() {
+        let mut rng = Rng::new(0x94d0_49bb_1331_11eb);
+
+        for _ in 0..CASES {
+            let moduli: Vec<i64> = (0..rng.range(1, 4)).map(|_| rng.range(1, 60)).collect();
+            let x = rng.range(0, 1_000_000);
+            let congruences = moduli.iter().map(|n| (x % n, *n));
+
+            let (solution, modulus) = crt(congruences).unwrap();
+            assert_eq!(modulus, lcm_all(moduli.iter().copied()).unwrap());
+            assert_eq!(solution, x % modulus);
+        }
+
+        assert_eq!(crt([(2_i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1_i32, 4), (2, 6)]), None);
+        assert_eq!(crt([(1_i32, 0)]), None);
+
+        let large = (1_i128 << 125) - 1;
+        let (x, modulus) = crt([(5, large), (2, 3)]).unwrap();
+        assert_eq!(modulus, large * 3);
+        assert_eq!((x % large, x % 3), (5, 2));
+        assert_eq!(crt([(1_i128, i128::MAX), (0, 4)]), None);
+    }
+
+    #[test]
+    fn isqrt_rounds_down() {
+        let mut rng = Rng::new(0xbf58_476d_1ce4_e5b9);
+
+        for _ in 0..CASES {
+            let n = rng.next_u64();
+            let root = isqrt(n);
+            assert!(u128::from(root) * u128::from(root) <= u128::from(n));
+            assert!(u128::from(root + 1) * u128::from(root + 1) > u128::from(n));
+        }
+
+        assert_eq!(isqrt(u128::MAX), u128::from(u64::MAX));
+        assert_eq!(isqrt(0_i32), 0);
+    }
+
+    #[test]
+    fn binomials_match_pascals_triangle() {
+        for n in 1..60_i64 {
+            for k in 1..n {
+                assert_eq!(
+                    binomial(n, k),
+                    Some(binomial(n - 1, k - 1).unwrap() + binomial(n - 1, k).unwrap())
+                );
+            }
+        }
+
+        assert_eq!(binomial(5_u8, 7), Some(0));
+        assert_eq!(binomial(5_i32, -1), Some(0));
+        assert_eq!(binomial(-3_i32, 2), Some(6));
+        assert_eq!(binomial(66_u64, 33), Some(7_219_428_434_016_265_740));
+        assert_eq!(binomial(68_u64, 34), None);
+    }
+
+    #[test]
+    fn extrapolates_polynomials() {
+        let mut rng = Rng::new(0x8cb9_2ba7_2f3d_8dd7);
+
+        for _ in 0..CASES / 4 {
+            let coefficients: Vec<i128> = (0..rng.range(1, 5))
+                .map(|_| i128::from(rng.range(-100, 100)))
+                .collect();
+            let eval = |x: i128| coefficients.iter().rev().fold(0, |acc, c| acc * x + c);
+
+            let values: Vec<i128> = (0..coefficients.len() as i128).map(eval).collect();
+            let x = i128::from(rng.range(-1000, 26_501_365));
+            assert_eq!(extrapolate(&values, x), Some(eval(x)));
+
+            let points: Vec<(i128, i128)> = (0..coefficients.len())
+                .map(|_| i128::from(rng.range(-50, 50)))
+                .map(|px| (px, eval(px)))
+                .collect();
+            let distinct = points
+                .iter()
+                .enumerate()
+                .all(|(i, p)| points[..i].iter().all(|q| q.0 != p.0));
+            let x = i128::from(rng.range(-1000, 1_000_000));
+            if distinct {
+                assert_eq!(lagrange(&points, x), Some(eval(x)));
+            }
+        }
+
+        assert_eq!(extrapolate(&[0_i64, 3, 6, 9, 12, 15], 6), Some(18));
+        assert_eq!(extrapolate(&[10_i64, 13, 16, 21, 30, 45], -1), Some(5));
+        assert_eq!(extrapolate::<i64>(&[], 3), None);
+
+        assert_eq!(lagrange(&[(0_i64, 0), (2, 1)], 1), None);
+        assert_eq!(lagrange(&[(1_i64, 1), (1, 2)], 3), None);
+        assert_eq!(lagrange(&[(1_i64, 1), (1, 2)], 1), None);
+        assert_eq!(lagrange(&[(1_i64, 1), (2, 2), (1, 1)], 2), None);
+        assert_eq!(lagrange(&[(1_i64, 5)], 1), Some(5));
+    }
+}
@@ -0,0 +1,492 @@
+//! Helpers to read the common shapes of puzzle inputs: numbers scattered over a line, blocks separated by
+//! blank lines and lines that follow a pattern like `Button A: X+{}, Y+{}`.
+//!
+//! Everything borrows from the input instead of copying it, and errors report the line and column of the
+//! offending text. Lines and columns start at 1.
+
+use std::any::type_name;
+use std::error::Error;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// A value could not be read as the expected type, e.g. a number that overflows.
+    InvalidValue {
+        line: usize,
+        column: usize,
+        value: String,
+        expected: &'static str,
+    },
+    /// The text doesn't contain the literal text of a pattern.
+    Mismatch {
+        line: usize,
+        column: usize,
+        expected: String,
+    },
+    /// A pattern has a different number of `{}` placeholders than the tuple it is read into.
+    Placeholders { expected: usize, found: usize },
+}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseError::InvalidValue {
+                line,
+                column,
+                value,
+                expected,
+            } => write!(
+                f,
+                "cannot read `{value}` as {expected} at line {line}, column {column}."
+            ),
+            ParseError::Mismatch {
+                line,
+                column,
+                expected,
+            } => write!(f, "expected `{expected}` at line {line}, column {column}."),
+            ParseError::Placeholders { expected, found } => write!(
+                f,
+                "the pattern has {found} placeholders, expected {expected}."
+            ),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// The column of the byte `offset` in `text`, counted in characters.
+fn column(text: &str, offset: usize) -> usize {
+    text[..offset].chars().count() + 1
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A line of the input together with its line number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Line<'a> {
+    pub number: usize,
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// The integers of the line, see [`ints`].
+    pub fn ints<T: FromStr>(&self) -> Ints<'a, T> {
+        Ints::new(self.text, self.number)
+    }
+
+    /// Reads the whole line, without surrounding whitespace, as a single value.
+    pub fn value<T: FromField<'a>>(&self) -> Result<T, ParseError> {
+        let trimmed = self.text.trim();
+        let offset = self.text.len() - self.text.trim_start().len();
+        read_field(trimmed, self.number, column(self.text, offset))
+    }
+
+    /// Reads the line against a pattern, see [`pattern`].
+    pub fn pattern<P: FromFields<'a>>(&self, format: &str) -> Result<P, ParseError> {
+        read_pattern(self.text, format, self.number)
+    }
+}
+
+/// The lines of the input with their line numbers.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(index, text)| Line {
+        number: index + 1,
+        text,
+    })
+}
+
+/// Consecutive non-blank lines of the input, e.g. the rules and updates of a puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Block<'a> {
+    /// The line number of the first line of the block.
+    pub line: usize,
+    /// The lines of the block, without the line break after the last line.
+    pub text: &'a str,
+}
+
+impl<'a> Block<'a> {
+    /// The lines of the block, numbered as lines of the whole input.
+    pub fn lines(&self) -> impl Iterator<Item = Line<'a>> + use<'a> {
+        let first = self.line;
+        self.text
+            .lines()
+            .enumerate()
+            .map(move |(index, text)| Line {
+                number: first + index,
+                text,
+            })
+    }
+
+    /// The integers of the block, see [`ints`].
+    pub fn ints<T: FromStr>(&self) -> Ints<'a, T> {
+        Ints::new(self.text, self.line)
+    }
+}
+
+/// Splits the input into blocks that are separated by blank lines. Lines with only whitespace count as blank.
+pub fn blocks(input: &str) -> impl Iterator<Item = Block<'_>> {
+    let mut offset = 0;
+    let mut number = 0;
+    // NOTE: the start and end offsets and the first line number of the block that is being read.
+    let mut current: Option<(usize, usize, usize)> = None;
+    let mut lines = input.split_inclusive('\n');
+
+    std::iter::from_fn(move || {
+        loop {
+            let Some(raw) = lines.next() else {
+                return current.take().map(|(start, end, line)| Block {
+                    line,
+                    text: &input[start..end],
+                });
+            };
+
+            let start = offset;
+            offset += raw.len();
+            number += 1;
+
+            let text = raw.trim_end_matches(['\n', '\r']);
+            if text.trim().is_empty() {
+                if let Some((start, end, line)) = current.take() {
+                    return Some(Block {
+                        line,
+                        text: &input[start..end],
+                    });
+                }
+            } else {
+                let end = start + text.len();
+                match &mut current {
+                    Some((_, block_end, _)) => *block_end = end,
+                    None => current = Some((start, end, number)),
+                }
+            }
+        }
+    })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An iterator over the integers of a text, created by [`ints`].
+pub struct Ints<'a, T> {
+    text: &'a str,
+    offset: usize,
+    line: usize,
+    line_start: usize,
+    value: PhantomData<T>,
+}
+
+impl<'a, T> Ints<'a, T> {
+    fn new(text: &'a str, line: usize) -> Self {
+        Self {
+            text,
+            offset: 0,
+            line,
+            line_start: 0,
+            value: PhantomData,
+        }
+    }
+}
+
+impl<T: FromStr> Iterator for Ints<'_, T> {
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.text.as_bytes();
+
+        while self.offset < bytes.len() {
+            let start = self.offset;
+            let byte = bytes[start];
+
+            if byte == b'\n' {
+                self.line += 1;
+                self.line_start = start + 1;
+                self.offset += 1;
+                continue;
+            }
+
+            // NOTE: a minus between digits is a range like `1-3`, not a sign.
+            let is_sign = byte == b'-'
+                && bytes.get(start + 1).is_some_and(u8::is_ascii_digit)
+                && (start == 0 || !bytes[start - 1].is_ascii_digit());
+
+            if !is_sign && !byte.is_ascii_digit() {
+                self.offset += 1;
+                continue;
+            }
+
+            let mut end = start + 1;
+            while end < bytes.len() && bytes[end].is_ascii_digit() {
+                end += 1;
+            }
+            self.offset = end;
+
+            let value = &self.text[start..end];
+            return Some(value.parse().map_err(|_| ParseError::InvalidValue {
+                line: self.line,
+                column: column(&self.text[self.line_start..], start - self.line_start),
+                value: value.to_string(),
+                expected: type_name::<T>(),
+            }));
+        }
+
+        None
+    }
+}
+
+/// The integers in a text, e.g. `[3, -4, 12]` for `p=3,-4 v=12`. Anything other than digits separates them.
+/// A `-` directly before a number is its sign, unless it follows a digit, so `1-3` reads as `[1, 3]`.
+///
+/// Yields an error for numbers that don't fit `T`, e.g. negative numbers for unsigned types.
+/// Collect into `Result<Vec<T>, ParseError>` to stop at the first error.
+pub fn ints<T: FromStr>(text: &str) -> Ints<'_, T> {
+    Ints::new(text, 1)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A value that a placeholder of a pattern can be read as.
+pub trait FromField<'a>: Sized {
+    /// Reads the text of a placeholder, `None` if it isn't a valid value.
+    fn from_field(field: &'a str) -> Option<Self>;
+}
+
+impl<'a> FromField<'a> for &'a str {
+    fn from_field(field: &'a str) -> Option<Self> {
+        Some(field)
+    }
+}
+
+impl FromField<'_> for char {
+    fn from_field(field: &str) -> Option<Self> {
+        let mut chars = field.chars();
+        chars.next().filter(|_| chars.next().is_none())
+    }
+}
+
+macro_rules! impl_from_field {
+    ($($t:ty),*) => {
+        $(impl FromField<'_> for $t {
+            fn from_field(field: &str) -> Option<Self> {
+                field.parse().ok()
+            }
+        })*
+    };
+}
+
+impl_from_field!(
+    u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64, bool, String
+);
+
+fn read_field<'a, T: FromField<'a>>(
+    field: &'a str,
+    line: usize,
+    column: usize,
+) -> Result<T, ParseError> {
+    T::from_field(field).ok_or_else(|| ParseError::InvalidValue {
+        line,
+        column,
+        value: field.to_string(),
+        expected: type_name::<T>(),
+    })
+}
+
+/// A tuple of values that a pattern is read into, e.g. `(i64, i64)` for `Button A: X+{}, Y+{}`.
+pub trait FromFields<'a>: Sized {
+    /// The number of values of the tuple.
+    const COUNT: usize;
+
+    /// Reads the values from the captured placeholders, given with their columns.
+    fn from_fields(fields: &[(usize, &'a str)], line: usize) -> Result<Self, ParseError>;
+}
+
+macro_rules! impl_from_fields {
+    ($count:literal; $($t:ident),+) => {
+        impl<'a, $($t: FromField<'a>),+> FromFields<'a> for ($($t,)+) {
+            const COUNT: usize = $count;
+
+            fn from_fields(fields: &[(usize, &'a str)], line: usize) -> Result<Self, ParseError> {
+                let mut fields = fields.iter();
+                Ok(($({
+                    let (column, field) = fields.next().unwrap();
+                    read_field::<$t>(field, line, *column)?
+                },)+))
+            }
+        }
+    };
+}
+
+impl_from_fields!(1; A);
+impl_from_fields!(2; A, B);
+impl_from_fields!(3; A, B, C);
+impl_from_fields!(4; A, B, C, D);
+impl_from_fields!(5; A, B, C, D, E);
+impl_from_fields!(6; A, B, C, D, E, F);
+
+/// Reads a line that follows `format`, where every `{}` is a placeholder for a value, e.g.
+/// `pattern::<(i64, i64)>("Button A: X+94, Y+34", "Button A: X+{}, Y+{}")` returns `(94, 34)`.
+///
+/// A placeholder extends up to the next occurrence of the text after it, or to the end of the line for the last one.
+/// Values can be numbers, `char`, `bool`, `String` or `&str` slices of the line.
+pub fn pattern<'a, P: FromFields<'a>>(text: &'a str, format: &str) -> Result<P, ParseError> {
+    read_pattern(text, format, 1)
+}
+
+fn read_pattern<'a, P: FromFields<'a>>(
+    text: &'a str,
+    format: &str,
+    line: usize,
+) -> Result<P, ParseError> {
+    let mut literals = format.split("{}");
+    // NOTE: splitting always yields at least one part, the text before the first placeholder.
+    let prefix = literals.next().unwrap_or_default();
+    let literals: Vec<&str> = literals.collect();
+
+    if literals.len() != P::COUNT {
+        return Err(ParseError::Placeholders {
+            expected: P::COUNT,
+            found: literals.len(),
+        });
+    }
+
+    let mismatch = |offset: usize, expected: &str| ParseError::Mismatch {
+        line,
+        column: column(text, offset),
+        expected: expected.to_string(),
+    };
+
+    if !text.starts_with(prefix) {
+        return Err(mismatch(0, prefix));
+    }
+
+    let mut offset = prefix.len();
+    let mut fields = Vec::with_capacity(literals.len());
+
+    for (index, literal) in literals.iter().enumerate() {
+        let rest = &text[offset..];
+        let length = if index + 1 == literals.len() {
+            rest.strip_suffix(literal).map(str::len)
+        } else {
+            rest.find(literal)
+        }
+        .ok_or_else(|| mismatch(offset, literal))?;
+
+        fields.push((column(text, offset), &rest[..length]));
+        offset += length + literal.len();
+    }
+
+    P::from_fields(&fields, line)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Line, ParseError, blocks, ints, lines, pattern};
+
+    #[test]
+    fn extracts_signed_ints() {
+        let values: Result<Vec<i64>, _> = ints("p=3,-4 v=12,-305").collect();
+        assert_eq!(values, Ok(vec![3, -4, 12, -305]));
+
+        let values: Result<Vec<u32>, _> = ints("1-3 a: 10, 200").collect();
+        assert_eq!(values, Ok(vec![1, 3, 10, 200]));
+
+        assert_eq!(ints::<i32>("- -- abc").count(), 0);
+    }
+
+    #[test]
+    fn reports_invalid_ints() {
+        let values: Result<Vec<u8>, _> = ints("1 2\nx 300 4").collect();
+        assert_eq!(
+            values,
+            Err(ParseError::InvalidValue {
+                line: 2,
+                column: 3,
+                value: "300".into(),
+                expected: "u8",
+            })
+        );
+
+        let line = Line {
+            number: 7,
+            text: "é -1",
+        };
+        assert_eq!(
+            line.ints::<u64>().next().unwrap().unwrap_err().to_string(),
+            "cannot read `-1` as u64 at line 7, column 3."
+        );
+    }
+
+    #[test]
+    fn splits_blocks() {
+        let input = "\n47|53\n97|13\n\n\n75,47,61\r\n  \r\n97,61\n";
+        let blocks: Vec<_> = blocks(input).collect();
+
+        assert_eq!(blocks.len(), 3);
+        assert_eq!((blocks[0].line, blocks[0].text), (2, "47|53\n97|13"));
+        assert_eq!((blocks[1].line, blocks[1].text), (6, "75,47,61"));
+        assert_eq!((blocks[2].line, blocks[2].text), (8, "97,61"));
+
+        let numbers: Vec<usize> = blocks[0].lines().map(|line| line.number).collect();
+        assert_eq!(numbers, vec![2, 3]);
+
+        let values: Result<Vec<u32>, _> = blocks[2].ints().collect();
+        assert_eq!(values, Ok(vec![97, 61]));
+
+        assert_eq!(super::blocks("").count(), 0);
+    }
+
+    #[test]
+    fn reads_patterns() {
+        assert_eq!(
+            pattern::<(i64, i64)>("Button A: X+94, Y+34", "Button A: X+{}, Y+{}"),
+            Ok((94, 34))
+        );
+        assert_eq!(
+            pattern::<(&str, char, u8)>("move abc to x (3)", "move {} to {} ({})"),
+            Ok(("abc", 'x', 3))
+        );
+
+        let line = lines("a\nPrize: X=8400, Y=5400").nth(1).unwrap();
+        assert_eq!(line.pattern("Prize: X={}, Y={}"), Ok((8400_u32, 5400_u32)));
+        assert_eq!(line.number, 2);
+    }
+
+    #[test]
+    fn reports_pattern_errors() {
+        assert_eq!(
+            pattern::<(i64, i64)>("Button A: X+94; Y+34", "Button A: X+{}, Y+{}"),
+            Err(ParseError::Mismatch {
+                line: 1,
+                column: 13,
+                expected: ", Y+".into(),
+            })
+        );
+        assert_eq!(
+            pattern::<(i64,)>("Button A: X+9z", "Button A: X+{}"),
+            Err(ParseError::InvalidValue {
+                line: 1,
+                column: 13,
+                value: "9z".into(),
+                expected: "i64",
+            })
+        );
+        assert_eq!(
+            pattern::<(i64, i64)>("x", "{}"),
+            Err(ParseError::Placeholders {
+                expected: 2,
+                found: 1,
+            })
+        );
+
+        let line = Line {
+            number: 4,
+            text: "  12x ",
+        };
+        assert_eq!(
+            line.value::<u32>().unwrap_err().to_string(),
+            "cannot read `12x` as u32 at line 4, column 3."
+        );
+    }
+}
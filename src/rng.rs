@@ -0,0 +1,31 @@
+//! A seeded xorshift generator for the property tests of the library modules.
+//!
+//! Tests pick a fixed seed, so every run checks the same values and a failing case can be reproduced.
+
+pub struct Rng(u64);
+
+impl Rng {
+    /// Creates a generator. The seed must not be 0, which xorshift never leaves.
+    pub fn new(seed: u64) -> Self {
+        assert_ne!(seed, 0, "expected a seed other than 0.");
+        Self(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    /// A value in `0..n`.
+    pub fn below(&mut self, n: u64) -> u64 {
+        self.next_u64() % n
+    }
+
+    /// A value in `min..=max`.
+    pub fn range(&mut self, min: i64, max: i64) -> i64 {
+        let span = (i128::from(max) - i128::from(min) + 1) as u64;
+        min + (self.next_u64() % span) as i64
+    }
+}
@@ -0,0 +1,554 @@
+//! Generic searches over graphs that are given by a successor function, and cycle detection for simulations.
+//!
+//! Nodes can be any `Clone + Eq + Hash` type, e.g. a `grid::Point` or a `(Point, Direction)` state.
+//! Successor functions return the neighbors of a node, for weighted searches together with the cost of the step.
+
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A cost of a weighted search, e.g. `u32` or `u64`. `Default` is the cost of the empty path.
+pub trait Cost: Copy + Ord + Add<Output = Self> + Default {}
+
+impl<T: Copy + Ord + Add<Output = T> + Default> Cost for T {}
+
+/// A path found by a search, from the start to the goal (both inclusive).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// Follows the parents from `goal` back to the node without a parent, the start of the search.
+/// Returns the path from the start to `goal`.
+pub fn reconstruct_path<N: Clone + Eq + Hash>(parents: &HashMap<N, N>, goal: &N) -> Vec<N> {
+    let mut path = vec![goal.clone()];
+    let mut node = goal;
+    while let Some(parent) = parents.get(node) {
+        path.push(parent.clone());
+        node = parent;
+    }
+    path.reverse();
+    path
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Finds a path with the fewest steps from `start` to a node that satisfies `is_goal`.
+pub fn bfs<N, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut parents: HashMap<N, N> = HashMap::new();
+    let mut seen = HashSet::from([start.clone()]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if is_goal(&node) {
+            return Some(Path {
+                nodes: reconstruct_path(&parents, &node),
+                cost: steps,
+            });
+        }
+
+        for next in successors(&node) {
+            if seen.insert(next.clone()) {
+                parents.insert(next.clone(), node.clone());
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// The fewest steps from `start` to every node it reaches, e.g. to flood-fill a region.
+pub fn bfs_distances<N, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((node, steps)) = queue.pop_front() {
+        for next in successors(&node) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An entry of the priority queue, ordered so that `BinaryHeap` pops the lowest priority first.
+struct Queued<N, C> {
+    priority: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> PartialEq for Queued<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.priority == other.priority
+    }
+}
+
+impl<N, C: Ord> Eq for Queued<N, C> {}
+
+impl<N, C: Ord> PartialOrd for Queued<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> Ord for Queued<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.priority.cmp(&self.priority)
+    }
+}
+
+/// Finds a cheapest path from `start` to a node that satisfies `is_goal`. Step costs must not be negative.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, successors, |_| C::default(), is_goal)
+}
+
+/// Finds a cheapest path like [`dijkstra`], visiting nodes that `heuristic` estimates closer to the goal first.
+/// The heuristic must never overestimate the remaining cost, e.g. the manhattan distance on a grid.
+pub fn astar<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs: HashMap<N, C> = HashMap::from([(start.clone(), C::default())]);
+    let mut parents: HashMap<N, N> = HashMap::new();
+    let mut queue = BinaryHeap::from([Queued {
+        priority: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        // NOTE: nodes are queued again when a cheaper path is found, skip the outdated entries.
+        if costs.get(&node).is_some_and(|best| cost > *best) {
+            continue;
+        }
+
+        if is_goal(&node) {
+            return Some(Path {
+                nodes: reconstruct_path(&parents, &node),
+                cost,
+            });
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|best| next_cost < *best) {
+                costs.insert(next.clone(), next_cost);
+                parents.insert(next.clone(), node.clone());
+                queue.push(Queued {
+                    priority: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// The cost of a cheapest path from `start` to every node it reaches. Step costs must not be negative.
+pub fn dijkstra_costs<N, C, I>(start: N, mut successors: impl FnMut(&N) -> I) -> HashMap<N, C>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs: HashMap<N, C> = HashMap::from([(start.clone(), C::default())]);
+    let mut queue = BinaryHeap::from([Queued {
+        priority: C::default(),
+        cost: C::default(),
+        node: start,
+    }]);
+
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        if costs.get(&node).is_some_and(|best| cost > *best) {
+            continue;
+        }
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            if costs.get(&next).is_none_or(|best| next_cost < *best) {
+                costs.insert(next.clone(), next_cost);
+                queue.push(Queued {
+                    priority: next_cost,
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+
+    costs
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// All cheapest paths from a start to the goal nodes with the lowest cost.
+#[derive(Clone, Debug)]
+pub struct ShortestPaths<N, C> {
+    pub cost: C,
+    /// The number of distinct cheapest paths. Saturates at `u64::MAX`.
+    pub count: u64,
+    /// Every node that lies on at least one of the cheapest paths, including the start and goals.
+    pub nodes: HashSet<N>,
+}
+
+/// Finds every cheapest path from `start` to the nodes that satisfy `is_goal`. Step costs must be positive,
+/// otherwise paths with zero-cost loops could not be counted.
+pub fn shortest_paths<N, C, I>(
+    start: N,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<ShortestPaths<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Cost,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut costs: HashMap<N, C> = HashMap::from([(start.clone(), C::default())]);
+    let mut counts: HashMap<N, u64> = HashMap::from([(start.clone(), 1)]);
+    let mut parents: HashMap<N, Vec<N>> = HashMap::new();
+    let mut queue = BinaryHeap::from([Queued {
+        priority: C::default(),
+        cost: C::default(),
+        node: start,
+    }]);
+
+    let mut best: Option<C> = None;
+    let mut goals = vec![];
+
+    while let Some(Queued { cost, node, .. }) = queue.pop() {
+        if costs.get(&node).is_some_and(|best| cost > *best) {
+            continue;
+        }
+        if best.is_some_and(|best| cost > best) {
+            break;
+        }
+
+        if is_goal(&node) {
+            best = Some(cost);
+            goals.push(node);
+            continue;
+        }
+
+        // NOTE: with positive step costs, every parent of a node is popped before the node, so its count is final.
+        let count = counts[&node];
+
+        for (next, step) in successors(&node) {
+            let next_cost = cost + step;
+            match costs.get(&next).map(|best| next_cost.cmp(best)) {
+                None | Some(Ordering::Less) => {
+                    costs.insert(next.clone(), next_cost);
+                    counts.insert(next.clone(), count);
+                    parents.insert(next.clone(), vec![node.clone()]);
+                    queue.push(Queued {
+                        priority: next_cost,
+                        cost: next_cost,
+                        node: next,
+                    });
+                }
+                Some(Ordering::Equal) => {
+                    let total = counts.entry(next.clone()).or_default();
+                    *total = total.saturating_add(count);
+                    parents.entry(next).or_default().push(node.clone());
+                }
+                Some(Ordering::Greater) => {}
+            }
+        }
+    }
+
+    let cost = best?;
+    let count = goals
+        .iter()
+        .fold(0_u64, |total, goal| total.saturating_add(counts[goal]));
+
+    let mut nodes: HashSet<N> = HashSet::new();
+    let mut stack = goals;
+    while let Some(node) = stack.pop() {
+        if let Some(node_parents) = parents.get(&node) {
+            stack.extend(
+                node_parents
+                    .iter()
+                    .filter(|parent| !nodes.contains(*parent))
+                    .cloned(),
+            );
+        }
+        nodes.insert(node);
+    }
+
+    Some(ShortestPaths { cost, count, nodes })
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The cycle of a sequence of states that repeats, e.g. a simulation that ends up in a loop.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    /// The number of steps before the first state of the cycle.
+    pub start: usize,
+    /// The number of steps after which the states repeat.
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`, e.g. to find the state after one billion steps.
+    #[must_use]
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.start {
+            n
+        } else {
+            self.start + (n - self.start) % self.length
+        }
+    }
+}
+
+/// Finds the cycle of the states `start`, `f(start)`, `f(f(start))`, ... with Brent's algorithm.
+/// Only keeps two states in memory and calls `f` fewer times than [`floyd`].
+///
+/// NOTE: never returns if the states don't repeat, so the state space must be finite.
+pub fn brent<S: Clone + PartialEq>(start: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = f(&start);
+
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = f(&hare);
+        length += 1;
+    }
+
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..length {
+        hare = f(&hare);
+    }
+
+    let mut cycle_start = 0;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        cycle_start += 1;
+    }
+
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+/// Finds the cycle of the states `start`, `f(start)`, `f(f(start))`, ... with Floyd's algorithm.
+///
+/// NOTE: never returns if the states don't repeat, so the state space must be finite.
+pub fn floyd<S: Clone + PartialEq>(start: S, mut f: impl FnMut(&S) -> S) -> Cycle {
+    let mut tortoise = f(&start);
+    let mut hare = f(&tortoise);
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        let next = f(&hare);
+        hare = f(&next);
+    }
+
+    let mut cycle_start = 0;
+    tortoise = start;
+    while tortoise != hare {
+        tortoise = f(&tortoise);
+        hare = f(&hare);
+        cycle_start += 1;
+    }
+
+    let mut length = 1;
+    hare = f(&tortoise);
+    while tortoise != hare {
+        hare = f(&hare);
+        length += 1;
+    }
+
+    Cycle {
+        start: cycle_start,
+        length,
+    }
+}
+
+/// The state after `n` steps of `f`, skipping the repetitions of its cycle.
+pub fn nth_state<S: Clone + PartialEq>(start: S, mut f: impl FnMut(&S) -> S, n: usize) -> S {
+    let cycle = brent(start.clone(), &mut f);
+    let mut state = start;
+    for _ in 0..cycle.reduce(n) {
+        state = f(&state);
+    }
+    state
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{
+        astar, bfs, bfs_distances, brent, dijkstra, dijkstra_costs, floyd, nth_state,
+        shortest_paths,
+    };
+    use crate::grid::{Grid, Point};
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.####.#.
+......#E";
+
+    fn open_neighbors(grid: &Grid<char>, point: Point) -> Vec<Point> {
+        grid.neighbors4(point).filter(|p| grid[*p] != '#').collect()
+    }
+
+    #[test]
+    fn finds_fewest_steps() {
+        let grid = Grid::parse(MAZE).unwrap();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+
+        let path = bfs(start, |p| open_neighbors(&grid, *p), |p| *p == end).unwrap();
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.nodes.len(), 16);
+        assert_eq!(path.nodes.first(), Some(&start));
+        assert_eq!(path.nodes.last(), Some(&end));
+        assert!(path.nodes.windows(2).all(|w| w[0].manhattan(w[1]) == 1));
+
+        let distances = bfs_distances(start, |p| open_neighbors(&grid, *p));
+        assert_eq!(distances[&end], 15);
+        assert_eq!(distances.len(), grid.positions(|c| *c != '#').count());
+
+        assert!(bfs(start, |p| open_neighbors(&grid, *p), |_| false).is_none());
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        // a -1-> b -1-> c -1-> d is cheaper than a -5-> d.
+        let edges = |node: &char| match node {
+            'a' => vec![('b', 1_u32), ('d', 5)],
+            'b' => vec![('c', 1)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        };
+
+        let path = dijkstra('a', edges, |n| *n == 'd').unwrap();
+        assert_eq!(path.cost, 3);
+        assert_eq!(path.nodes, vec!['a', 'b', 'c', 'd']);
+
+        let costs = dijkstra_costs('a', edges);
+        assert_eq!(costs[&'c'], 2);
+        assert_eq!(costs.len(), 4);
+
+        assert!(dijkstra('d', edges, |n| *n == 'a').is_none());
+    }
+
+    #[test]
+    fn finds_paths_with_heuristic() {
+        let grid = Grid::parse(MAZE).unwrap();
+        let start = grid.find(&'S').unwrap();
+        let end = grid.find(&'E').unwrap();
+
+        let path = astar(
+            start,
+            |p| open_neighbors(&grid, *p).into_iter().map(|p| (p, 1_u64)),
+            |p| p.manhattan(end),
+            |p| *p == end,
+        )
+        .unwrap();
+
+        assert_eq!(path.cost, 15);
+        assert_eq!(path.nodes.last(), Some(&end));
+    }
+
+    #[test]
+    fn counts_shortest_paths() {
+        let grid = Grid::new(3, 3, '.');
+        let end = Point::new(2, 2);
+
+        let paths = shortest_paths(
+            Point::ORIGIN,
+            |p| grid.neighbors4(*p).map(|p| (p, 1_u32)).collect::<Vec<_>>(),
+            |p| *p == end,
+        )
+        .unwrap();
+
+        assert_eq!(paths.cost, 4);
+        assert_eq!(paths.count, 6);
+        assert_eq!(paths.nodes.len(), 9);
+
+        // two goals with the same cost: both paths count.
+        let edges = |node: &char| match node {
+            's' => vec![('a', 1_u32), ('b', 1), ('c', 2)],
+            'a' => vec![('x', 1)],
+            'b' => vec![('y', 1)],
+            'c' => vec![('x', 1)],
+            _ => vec![],
+        };
+        let paths = shortest_paths('s', edges, |n| matches!(n, 'x' | 'y')).unwrap();
+        assert_eq!(paths.cost, 2);
+        assert_eq!(paths.count, 2);
+        assert_eq!(paths.nodes, HashSet::from(['s', 'a', 'b', 'x', 'y']));
+    }
+
+    #[test]
+    fn detects_cycles() {
+        // 0 -> 1 -> 2 -> 3 -> 4 -> 2 -> ...
+        let f = |x: &u32| if *x == 4 { 2 } else { x + 1 };
+
+        let cycle = brent(0, f);
+        assert_eq!(cycle.start, 2);
+        assert_eq!(cycle.length, 3);
+        assert_eq!(floyd(0, f), cycle);
+
+        assert_eq!(cycle.reduce(1), 1);
+        assert_eq!(cycle.reduce(5), 2);
+        assert_eq!(nth_state(0, f, 1_000_000_000), 4);
+
+        // a sequence that starts in its cycle.
+        let cycle = brent(0, |x: &u32| (x + 1) % 4);
+        assert_eq!((cycle.start, cycle.length), (0, 4));
+    }
+}
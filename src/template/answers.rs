@@ -0,0 +1,351 @@
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::aoc_client::AnswerHint;
+use crate::template::{Day, Year};
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// The verdict the website returned for a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Wrong(Option<AnswerHint>),
+}
+
+/// Represents a single submitted answer.
+#[derive(Clone, Debug)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+/// The outcome of checking an answer against the ledger before submitting it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    /// Nothing is known about this answer.
+    Unknown,
+    /// The part was solved with this answer.
+    Accepted,
+    /// The part was solved with a different answer.
+    Regression(String),
+    /// This answer was submitted before and was wrong.
+    KnownWrong,
+    /// A previous, lower or equal answer was too high.
+    LikelyTooHigh(String),
+    /// A previous, higher or equal answer was too low.
+    LikelyTooLow(String),
+}
+
+/// Represents every answer submitted so far.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Submission>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns an empty ledger.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(ANSWERS_FILE_PATH)
+            .map_err(|x| x.to_string())
+            .and_then(Answers::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Records a submission, replacing an earlier submission of the same answer.
+    pub fn record(&mut self, submission: Submission) {
+        self.data.retain(|s| {
+            !(s.day == submission.day && s.part == submission.part && s.answer == submission.answer)
+        });
+        self.data.push(submission);
+        self.data.sort_by_key(|s| (s.day, s.part));
+    }
+
+    /// Returns the accepted answer of a part, if it has been solved.
+    pub fn accepted(&self, day: Day, part: u8) -> Option<&str> {
+        self.submissions(day, part)
+            .find(|s| s.verdict == Verdict::Correct)
+            .map(|s| s.answer.as_str())
+    }
+
+    /// Checks an answer against earlier submissions of the same part.
+    pub fn check(&self, day: Day, part: u8, answer: &str) -> Check {
+        if let Some(accepted) = self.accepted(day, part) {
+            return if accepted == answer {
+                Check::Accepted
+            } else {
+                Check::Regression(accepted.to_string())
+            };
+        }
+
+        if self.submissions(day, part).any(|s| s.answer == answer) {
+            return Check::KnownWrong;
+        }
+
+        let Ok(value) = answer.parse::<i128>() else {
+            return Check::Unknown;
+        };
+
+        let bound = |hint: AnswerHint| {
+            self.submissions(day, part)
+                .filter(move |s| s.verdict == Verdict::Wrong(Some(hint)))
+                .filter_map(|s| Some((s.answer.parse::<i128>().ok()?, s)))
+        };
+
+        if let Some((_, s)) = bound(AnswerHint::TooHigh)
+            .filter(|(x, _)| value >= *x)
+            .min_by_key(|(x, _)| *x)
+        {
+            return Check::LikelyTooHigh(s.answer.clone());
+        }
+
+        if let Some((_, s)) = bound(AnswerHint::TooLow)
+            .filter(|(x, _)| value <= *x)
+            .max_by_key(|(x, _)| *x)
+        {
+            return Check::LikelyTooLow(s.answer.clone());
+        }
+
+        Check::Unknown
+    }
+
+    fn submissions(&self, day: Day, part: u8) -> impl Iterator<Item = &Submission> {
+        self.data
+            .iter()
+            .filter(move |s| s.day == day && s.part == part)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.day.year().into_inner())),
+        );
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+
+        let (verdict, hint) = match value.verdict {
+            Verdict::Correct => ("correct", None),
+            Verdict::Wrong(None) => ("wrong", None),
+            Verdict::Wrong(Some(AnswerHint::TooHigh)) => ("wrong", Some("too_high")),
+            Verdict::Wrong(Some(AnswerHint::TooLow)) => ("wrong", Some("too_low")),
+        };
+
+        map.insert("verdict".into(), JsonValue::String(verdict.into()));
+        map.insert(
+            "hint".into(),
+            match hint {
+                Some(x) => JsonValue::String(x.into()),
+                None => JsonValue::Null,
+            },
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let year = json
+            .get("year")
+            .and_then(|v| v.get::<f64>())
+            .and_then(|year| Year::from_str(&year.to_string()).ok())
+            .ok_or("Expected submission.year to be a valid year.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str_in_year(day, year).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .filter(|part| **part == 1.0 || **part == 2.0)
+            .map(|part| if *part == 1.0 { 1 } else { 2 })
+            .ok_or("Expected submission.part to be 1 or 2.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let hint = match json.get("hint").and_then(|v| v.get::<String>()) {
+            Some(x) if x == "too_high" => Some(AnswerHint::TooHigh),
+            Some(x) if x == "too_low" => Some(AnswerHint::TooLow),
+            _ => None,
+        };
+
+        let verdict = match json.get("verdict").and_then(|v| v.get::<String>()) {
+            Some(x) if x == "correct" => Verdict::Correct,
+            Some(x) if x == "wrong" => Verdict::Wrong(hint),
+            _ => return Err("Expected submission.verdict to be `correct` or `wrong`.".into()),
+        };
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            verdict,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answers, Check, Submission, Verdict};
+    use crate::template::aoc_client::AnswerHint;
+    use crate::{day, year};
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            day: day!(7, year!(2023)),
+            part,
+            answer: answer.into(),
+            verdict,
+        }
+    }
+
+    fn get_mock_answers() -> Answers {
+        let mut answers = Answers::default();
+        answers.record(submission(
+            1,
+            "100",
+            Verdict::Wrong(Some(AnswerHint::TooHigh)),
+        ));
+        answers.record(submission(
+            1,
+            "10",
+            Verdict::Wrong(Some(AnswerHint::TooLow)),
+        ));
+        answers.record(submission(1, "50", Verdict::Wrong(None)));
+        answers.record(submission(2, "42", Verdict::Correct));
+        answers
+    }
+
+    #[test]
+    fn refuses_known_wrong_answers() {
+        let answers = get_mock_answers();
+        assert_eq!(
+            answers.check(day!(7, year!(2023)), 1, "50"),
+            Check::KnownWrong
+        );
+    }
+
+    #[test]
+    fn warns_about_bounds() {
+        let answers = get_mock_answers();
+        let day = day!(7, year!(2023));
+        assert_eq!(
+            answers.check(day, 1, "150"),
+            Check::LikelyTooHigh("100".into())
+        );
+        assert_eq!(answers.check(day, 1, "5"), Check::LikelyTooLow("10".into()));
+        assert_eq!(answers.check(day, 1, "60"), Check::Unknown);
+        assert_eq!(answers.check(day, 1, "abc"), Check::Unknown);
+    }
+
+    #[test]
+    fn compares_with_accepted_answer() {
+        let answers = get_mock_answers();
+        let day = day!(7, year!(2023));
+        assert_eq!(answers.accepted(day, 2), Some("42"));
+        assert_eq!(answers.accepted(day, 1), None);
+        assert_eq!(answers.check(day, 2, "42"), Check::Accepted);
+        assert_eq!(answers.check(day, 2, "43"), Check::Regression("42".into()));
+    }
+
+    #[test]
+    fn record_replaces_duplicate_answers() {
+        let mut answers = get_mock_answers();
+        answers.record(submission(1, "50", Verdict::Correct));
+        assert_eq!(answers.data.len(), 4);
+        assert_eq!(answers.accepted(day!(7, year!(2023)), 1), Some("50"));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let answers = get_mock_answers();
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Answers::try_from(json).unwrap();
+
+        assert_eq!(parsed.data.len(), answers.data.len());
+        for (a, b) in parsed.data.iter().zip(answers.data.iter()) {
+            assert_eq!(a.day, b.day);
+            assert_eq!(a.part, b.part);
+            assert_eq!(a.answer, b.answer);
+            assert_eq!(a.verdict, b.verdict);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "year": 2023, "day": "07", "part": 3 }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+}
@@ -0,0 +1,471 @@
+/// Native client for the Advent of Code website.
+///
+/// Reads the session cookie from the `AOC_SESSION` environment variable or from `~/.adventofcode.session`.
+/// The base URL can be overridden via `AOC_BASE_URL`, e.g. to point the client at a local mock server.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use ureq::{Agent, Body, http::Response};
+
+use crate::template::Day;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const SESSION_FILE_NAME: &str = ".adventofcode.session";
+const USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+
+/// Hint that is sent along with a wrong answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AnswerHint {
+    TooHigh,
+    TooLow,
+}
+
+#[derive(Debug)]
+pub enum AocClientError {
+    MissingSession,
+    Unauthorized,
+    NotUnlocked,
+    RateLimited(Option<String>),
+    WrongAnswer(Option<AnswerHint>),
+    AlreadySolved,
+    UnexpectedStatus(u16),
+    UnexpectedResponse,
+    Transport(ureq::Error),
+    IO(std::io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::MissingSession => write!(
+                f,
+                "no session cookie found. Set `AOC_SESSION` or create \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::Unauthorized => {
+                write!(f, "the session cookie was rejected, it might have expired.")
+            }
+            AocClientError::NotUnlocked => write!(f, "this puzzle has not been unlocked yet."),
+            AocClientError::RateLimited(Some(wait)) => {
+                write!(f, "you are being rate-limited, {wait}.")
+            }
+            AocClientError::RateLimited(None) => write!(f, "you are being rate-limited."),
+            AocClientError::WrongAnswer(Some(AnswerHint::TooHigh)) => {
+                write!(f, "that's not the right answer, your answer is too high.")
+            }
+            AocClientError::WrongAnswer(Some(AnswerHint::TooLow)) => {
+                write!(f, "that's not the right answer, your answer is too low.")
+            }
+            AocClientError::WrongAnswer(None) => write!(f, "that's not the right answer."),
+            AocClientError::AlreadySolved => write!(f, "this part has already been solved."),
+            AocClientError::UnexpectedStatus(status) => {
+                write!(f, "the server responded with status {status}.")
+            }
+            AocClientError::UnexpectedResponse => {
+                write!(f, "the server response could not be understood.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::IO(e) => write!(f, "could not access file: {e}"),
+        }
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        AocClientError::Transport(e)
+    }
+}
+
+impl From<std::io::Error> for AocClientError {
+    fn from(e: std::io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+pub struct AocClient {
+    agent: Agent,
+    base_url: String,
+    session: String,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = Agent::config_builder()
+            .http_status_as_error(false)
+            .max_redirects(0)
+            .user_agent(USER_AGENT)
+            .build()
+            .new_agent();
+
+        Self {
+            agent,
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+        }
+    }
+
+    /// Creates a client from the session cookie and base URL configured in the environment.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = read_session()?;
+        Ok(Self::new(&base_url, &session))
+    }
+
+    pub fn download_input(&self, day: Day) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(day));
+        let response = self
+            .agent
+            .get(&url)
+            .header("Cookie", self.cookie())
+            .call()?;
+        read_body(response)
+    }
+
+    /// Fetches the puzzle description, i.e. the `<article>` elements of the puzzle page.
+    pub fn fetch_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let response = self
+            .agent
+            .get(&self.day_url(day))
+            .header("Cookie", self.cookie())
+            .call()?;
+        let html = read_body(response)?;
+
+        let articles = extract_articles(&html);
+        if articles.is_empty() {
+            return Err(AocClientError::UnexpectedResponse);
+        }
+        Ok(articles.join("\n\n"))
+    }
+
+    /// Submits an answer. Returns `Ok` if the answer was correct.
+    pub fn submit(&self, day: Day, part: u8, answer: &str) -> Result<(), AocClientError> {
+        let url = format!("{}/answer", self.day_url(day));
+        let level = part.to_string();
+        let response = self
+            .agent
+            .post(&url)
+            .header("Cookie", self.cookie())
+            .send_form([("level", level.as_str()), ("answer", answer)])?;
+        let html = read_body(response)?;
+
+        let article = extract_articles(&html)
+            .into_iter()
+            .next()
+            .ok_or(AocClientError::UnexpectedResponse)?;
+
+        parse_verdict(article)
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, day.year(), day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+/// Reads the session cookie from `AOC_SESSION` or from `~/.adventofcode.session`.
+pub fn read_session() -> Result<String, AocClientError> {
+    if let Ok(session) = env::var("AOC_SESSION")
+        && !session.trim().is_empty()
+    {
+        return Ok(session.trim().to_string());
+    }
+
+    let path = home_dir()
+        .map(|home| home.join(SESSION_FILE_NAME))
+        .ok_or(AocClientError::MissingSession)?;
+
+    match fs::read_to_string(path) {
+        Ok(session) if !session.trim().is_empty() => Ok(session.trim().to_string()),
+        _ => Err(AocClientError::MissingSession),
+    }
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn read_body(mut response: Response<Body>) -> Result<String, AocClientError> {
+    let status = response.status().as_u16();
+    match status {
+        200..=299 => Ok(response.body_mut().read_to_string()?),
+        // NOTE: requests with an invalid session are redirected or rejected as bad requests.
+        300..=399 | 400 | 401 | 403 => Err(AocClientError::Unauthorized),
+        404 => Err(AocClientError::NotUnlocked),
+        429 => Err(AocClientError::RateLimited(None)),
+        status => Err(AocClientError::UnexpectedStatus(status)),
+    }
+}
+
+pub(crate) fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(len) = rest[start..].find("</article>") else {
+            break;
+        };
+        let end = start + len + "</article>".len();
+        articles.push(&rest[start..end]);
+        rest = &rest[end..];
+    }
+
+    articles
+}
+
+fn parse_verdict(article: &str) -> Result<(), AocClientError> {
+    if article.contains("That's the right answer") {
+        Ok(())
+    } else if article.contains("That's not the right answer") {
+        let hint = if article.contains("too high") {
+            Some(AnswerHint::TooHigh)
+        } else if article.contains("too low") {
+            Some(AnswerHint::TooLow)
+        } else {
+            None
+        };
+        Err(AocClientError::WrongAnswer(hint))
+    } else if article.contains("You gave an answer too recently") {
+        let wait = article
+            .split("You have ")
+            .nth(1)
+            .and_then(|s| s.split(" left to wait").next())
+            .map(|s| format!("{s} left to wait"));
+        Err(AocClientError::RateLimited(wait))
+    } else if article.contains("Did you already complete it") {
+        Err(AocClientError::AlreadySolved)
+    } else {
+        Err(AocClientError::UnexpectedResponse)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn get_input_path(day: Day) -> String {
+    format!("data/{}/inputs/{day}.txt", day.year())
+}
+
+pub(crate) fn get_puzzle_path(day: Day) -> String {
+    format!("data/{}/puzzles/{day}.md", day.year())
+}
+
+fn write_file(path: &str, contents: &str) -> Result<(), AocClientError> {
+    if let Some(dir) = Path::new(path).parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(path, contents)?;
+    Ok(())
+}
+
+/// Downloads input and puzzle description of a day to the `data` directory.
+pub fn download(day: Day) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+
+    let input_path = get_input_path(day);
+    let puzzle_path = get_puzzle_path(day);
+
+    write_file(&input_path, &client.download_input(day)?)?;
+    write_file(&puzzle_path, &client.fetch_puzzle(day)?)?;
+
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Fetches the puzzle description of a day, stores it in the `data` directory and returns it as plain text.
+pub fn read(day: Day) -> Result<String, AocClientError> {
+    let client = AocClient::from_env()?;
+    let puzzle = client.fetch_puzzle(day)?;
+    write_file(&get_puzzle_path(day), &puzzle)?;
+    Ok(html_to_text(&puzzle))
+}
+
+pub fn submit(day: Day, part: u8, result: &str) -> Result<(), AocClientError> {
+    AocClient::from_env()?.submit(day, part, result)
+}
+
+/// Converts puzzle HTML to plain text for display on the command-line.
+pub(crate) fn html_to_text(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread::{self, JoinHandle},
+    };
+
+    use super::{AnswerHint, AocClient, AocClientError, html_to_text};
+    use crate::{day, year};
+
+    /// Serves a single canned response and returns the raw request that was received.
+    fn mock_server(status: u16, body: &'static str) -> (String, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+
+            let mut request = String::new();
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut request_body = vec![0; content_length];
+            reader.read_exact(&mut request_body).unwrap();
+            request.push_str(&String::from_utf8(request_body).unwrap());
+
+            let response = format!(
+                "HTTP/1.1 {status} Mock\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (base_url, handle)
+    }
+
+    #[test]
+    fn downloads_input_with_session() {
+        let (base_url, server) = mock_server(200, "1\n2\n3\n");
+        let client = AocClient::new(&base_url, "abc");
+        let input = client.download_input(day!(7, year!(2023))).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(input, "1\n2\n3\n");
+        assert!(request.starts_with("GET /2023/day/7/input "));
+        assert!(request.contains("session=abc"));
+    }
+
+    #[test]
+    fn fetches_puzzle_articles() {
+        let (base_url, server) = mock_server(
+            200,
+            "<html><main><article class=\"day-desc\"><h2>--- Day 7 ---</h2></article><p>x</p></main></html>",
+        );
+        let client = AocClient::new(&base_url, "abc");
+        let puzzle = client.fetch_puzzle(day!(7, year!(2023))).unwrap();
+        server.join().unwrap();
+
+        assert_eq!(
+            puzzle,
+            "<article class=\"day-desc\"><h2>--- Day 7 ---</h2></article>"
+        );
+        assert_eq!(html_to_text(&puzzle), "--- Day 7 ---");
+    }
+
+    #[test]
+    fn maps_unauthorized() {
+        let (base_url, server) = mock_server(400, "Puzzle inputs differ by user.");
+        let client = AocClient::new(&base_url, "expired");
+        let res = client.download_input(day!(1, year!(2023)));
+        server.join().unwrap();
+        assert!(matches!(res, Err(AocClientError::Unauthorized)));
+    }
+
+    #[test]
+    fn maps_not_unlocked() {
+        let (base_url, server) = mock_server(404, "Please don't repeatedly request this endpoint.");
+        let client = AocClient::new(&base_url, "abc");
+        let res = client.download_input(day!(1, year!(2023)));
+        server.join().unwrap();
+        assert!(matches!(res, Err(AocClientError::NotUnlocked)));
+    }
+
+    #[test]
+    fn submits_correct_answer() {
+        let (base_url, server) = mock_server(
+            200,
+            "<article><p>That's the right answer! You are one gold star closer.</p></article>",
+        );
+        let client = AocClient::new(&base_url, "abc");
+        let res = client.submit(day!(7, year!(2023)), 2, "42");
+        let request = server.join().unwrap();
+
+        assert!(res.is_ok());
+        assert!(request.starts_with("POST /2023/day/7/answer "));
+        assert!(request.ends_with("level=2&answer=42"));
+    }
+
+    #[test]
+    fn maps_wrong_answer() {
+        let (base_url, server) = mock_server(
+            200,
+            "<article><p>That's not the right answer; your answer is too high.</p></article>",
+        );
+        let client = AocClient::new(&base_url, "abc");
+        let res = client.submit(day!(7, year!(2023)), 1, "9001");
+        server.join().unwrap();
+        assert!(matches!(
+            res,
+            Err(AocClientError::WrongAnswer(Some(AnswerHint::TooHigh)))
+        ));
+    }
+
+    #[test]
+    fn maps_rate_limit() {
+        let (base_url, server) = mock_server(
+            200,
+            "<article><p>You gave an answer too recently. You have 37s left to wait.</p></article>",
+        );
+        let client = AocClient::new(&base_url, "abc");
+        let res = client.submit(day!(7, year!(2023)), 1, "1");
+        server.join().unwrap();
+        match res {
+            Err(AocClientError::RateLimited(Some(wait))) => assert_eq!(wait, "37s left to wait"),
+            _ => panic!("expected rate limit, got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn maps_already_solved() {
+        let (base_url, server) = mock_server(
+            200,
+            "<article><p>You don't seem to be solving the right level.  Did you already complete it?</p></article>",
+        );
+        let client = AocClient::new(&base_url, "abc");
+        let res = client.submit(day!(7, year!(2023)), 1, "1");
+        server.join().unwrap();
+        assert!(matches!(res, Err(AocClientError::AlreadySolved)));
+    }
+}
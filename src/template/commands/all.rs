@@ -1,5 +1,7 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::registry::Entry;
+use crate::template::run_multi::{MultiOptions, run_multi};
+use crate::template::{AllDays, Year};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(registry: &[Entry], year: Year, options: MultiOptions) {
+    run_multi(registry, &AllDays::for_year(year).collect(), options);
 }
@@ -0,0 +1,68 @@
+use std::{fs, path::Path, process};
+
+use crate::template::{
+    Day,
+    aoc_client::get_puzzle_path,
+    examples::propose_files,
+    puzzle::{PartExample, extract_examples},
+};
+
+/// Extracts the examples of a day from its puzzle description, if it was downloaded.
+#[must_use]
+pub fn extract(day: Day) -> Option<Vec<PartExample>> {
+    let puzzle = fs::read_to_string(get_puzzle_path(day)).ok()?;
+    Some(extract_examples(&puzzle))
+}
+
+/// Writes the proposed example files and manifest of a day.
+/// Existing files with contents are only replaced with `overwrite`.
+pub fn write(day: Day, parts: &[PartExample], overwrite: bool) -> Result<(), std::io::Error> {
+    for (path, contents) in propose_files(day, parts) {
+        let is_empty = fs::read_to_string(&path).map_or(true, |s| s.trim().is_empty());
+        if !is_empty && !overwrite {
+            println!("Skipped \"{path}\", it already exists. Use `--overwrite` to replace it.");
+            continue;
+        }
+
+        if let Some(dir) = Path::new(&path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, contents)?;
+        println!("Created example file \"{path}\"");
+    }
+
+    Ok(())
+}
+
+pub fn handle(day: Day, overwrite: bool) {
+    let Some(parts) = extract(day) else {
+        eprintln!(
+            "No puzzle description found at \"{}\". Run `cargo download {day}` first.",
+            get_puzzle_path(day)
+        );
+        process::exit(1);
+    };
+
+    if parts.iter().all(|part| part.input.is_none()) {
+        eprintln!("Could not find an example input in the puzzle description.");
+        process::exit(1);
+    }
+
+    for (i, part) in parts.iter().enumerate() {
+        let part_number = i + 1;
+        if let Some(input) = &part.input {
+            println!("Part {part_number} example:");
+            println!("{}", input.trim_end());
+        }
+        match &part.answer {
+            Some(answer) => println!("Part {part_number} answer: {answer}"),
+            None => println!("Part {part_number} answer: not found"),
+        }
+        println!("---");
+    }
+
+    if let Err(e) = write(day, &parts, overwrite) {
+        eprintln!("Failed to write example files: {e}");
+        process::exit(1);
+    }
+}
@@ -0,0 +1,125 @@
+/// Profiles the CPU time of a single part with `perf` and writes its folded stacks to `data/profiles/`.
+///
+/// The solution is built in release mode with debug info, then `perf` samples it while the runner
+/// repeats the part for the requested duration.
+use std::path::PathBuf;
+use std::process::{self, Command, Stdio};
+use std::time::Duration;
+use std::{env, fs, io};
+
+use crate::template::profile::fold_perf_script;
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, run_multi::get_bin_name};
+
+static PROFILES_DIR: &str = "data/profiles";
+
+/// Samples per second. An odd frequency avoids sampling in lockstep with periodic work.
+const FREQUENCY: u32 = 997;
+
+pub fn handle(day: Day, part: u8, duration: Duration) {
+    if let Err(e) = fs::create_dir_all(PROFILES_DIR) {
+        eprintln!("Failed to create profiles directory: {e}");
+        process::exit(1);
+    }
+
+    let bin = get_bin_name(day);
+    let name = format!("{bin}-part{part}");
+    let recording = PathBuf::from(PROFILES_DIR).join(format!("{name}.perf.data"));
+    let folded = PathBuf::from(PROFILES_DIR).join(format!("{name}.folded"));
+
+    build(&bin);
+    record(&bin, &recording, part, duration);
+
+    let script = match perf_script(&recording) {
+        Ok(script) => script,
+        Err(e) => {
+            eprintln!("Failed to read the recorded samples: {e}");
+            process::exit(1);
+        }
+    };
+
+    if let Err(e) = fs::write(&folded, fold_perf_script(&script)) {
+        eprintln!("Failed to write folded stacks: {e}");
+        process::exit(1);
+    }
+
+    // NOTE: the recording is only an intermediate, the folded stacks are much smaller.
+    let _ = fs::remove_file(&recording);
+
+    println!("Wrote folded stacks to \"{}\".", folded.display());
+    println!(
+        "{ANSI_ITALIC}View them with e.g. `inferno-flamegraph {} > flamegraph.svg` or https://www.speedscope.app.{ANSI_RESET}",
+        folded.display()
+    );
+}
+
+/// Builds the solution in release mode, with the debug info that `perf` needs to resolve symbols.
+fn build(bin: &str) {
+    let status = Command::new("cargo")
+        .args(["build", "--release", "--bin", bin])
+        .env("CARGO_PROFILE_RELEASE_DEBUG", "true")
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to build solution: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Records the solution with `perf` while it repeats `part`.
+fn record(bin: &str, recording: &PathBuf, part: u8, duration: Duration) {
+    let target_dir = env::var("CARGO_TARGET_DIR").unwrap_or_else(|_| "target".into());
+    let executable = PathBuf::from(target_dir).join("release").join(bin);
+
+    let status = Command::new("perf")
+        .args(["record", "--quiet", "-F", &FREQUENCY.to_string()])
+        .args(["--call-graph", "dwarf", "-o"])
+        .arg(recording)
+        .arg("--")
+        .arg(executable)
+        .args(["--profile", &part.to_string()])
+        .args(["--profile-seconds", &duration.as_secs_f64().to_string()])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => {
+            eprintln!(
+                "`perf record` failed. You may need to allow profiling, e.g. with `sudo sysctl kernel.perf_event_paranoid=1`."
+            );
+            process::exit(status.code().unwrap_or(1));
+        }
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            eprintln!(
+                "Could not find `perf`. CPU profiling requires Linux `perf`, e.g. from the `linux-tools` or `linux-perf` package."
+            );
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to run `perf`: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Dumps the samples of a recording with `perf script`.
+fn perf_script(recording: &PathBuf) -> io::Result<String> {
+    let output = Command::new("perf")
+        .args(["script", "-i"])
+        .arg(recording)
+        .stderr(Stdio::inherit())
+        .output()?;
+
+    if !output.status.success() {
+        return Err(io::Error::other("`perf script` exited with an error"));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
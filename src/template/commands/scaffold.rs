@@ -1,15 +1,21 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
-
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+use crate::template::{
+    Day, Year,
+    aoc_client::get_puzzle_path,
+    commands::examples,
+    puzzle::{PartExample, extract_examples, extract_title},
+    run_multi::get_bin_name,
+    templates,
+};
 
 fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
     let mut file = OpenOptions::new();
     if overwrite {
         file.create(true);
@@ -20,6 +26,7 @@ fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error>
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
+    create_parent_dir(path)?;
     OpenOptions::new()
         .write(true)
         .create(true)
@@ -27,10 +34,85 @@ fn create_file(path: &str) -> Result<File, std::io::Error> {
         .open(path)
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Returns the example answer of a part that the scaffolded test can check.
+fn example_answer(parts: &[PartExample], index: usize) -> Option<&str> {
+    // NOTE: the scaffolded tests read `<day>.txt`, so part two is only filled in if it shares the example of part one.
+    parts
+        .get(index)
+        .filter(|part| index == 0 || part.input.is_none() || part.input == parts[0].input)
+        .and_then(|part| part.answer.as_deref())
+}
+
+/// The values of all placeholders a template can use.
+fn placeholders(
+    day: Day,
+    title: Option<String>,
+    parts: &[PartExample],
+) -> Vec<(&'static str, String)> {
+    let answer_type = |index: usize| {
+        let answer = parts.get(index).and_then(|part| part.answer.as_deref());
+        templates::answer_type(answer).to_string()
+    };
+
+    vec![
+        ("DAY_NUMBER", day.into_inner().to_string()),
+        ("DAY", day.to_string()),
+        ("YEAR", day.year().to_string()),
+        (
+            "PUZZLE_TITLE",
+            title.unwrap_or_else(|| format!("Day {}", day.into_inner())),
+        ),
+        ("PART_ONE_TYPE", answer_type(0)),
+        ("PART_TWO_TYPE", answer_type(1)),
+        (
+            "PART_ONE_EXPECTED",
+            templates::format_expected(example_answer(parts, 0)),
+        ),
+        (
+            "PART_TWO_EXPECTED",
+            templates::format_expected(example_answer(parts, 1)),
+        ),
+    ]
+}
+
+fn create_parent_dir(path: &str) -> Result<(), std::io::Error> {
+    match Path::new(path).parent() {
+        Some(dir) => fs::create_dir_all(dir),
+        None => Ok(()),
+    }
+}
+
+pub fn handle(day: Day, overwrite: bool, template: Option<&str>) {
+    let year = day.year();
+    let input_path = format!("data/{year}/inputs/{day}.txt");
+    let example_path = format!("data/{year}/examples/{day}.txt");
+    let module_path = format!("src/bin/{}.rs", get_bin_name(day));
+
+    let (template_path, template) = match templates::load(template, day) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            eprintln!("Available templates: {}", templates::list().join(", "));
+            process::exit(1);
+        }
+    };
+
+    let puzzle = fs::read_to_string(get_puzzle_path(day)).ok();
+    let title = puzzle.as_deref().and_then(extract_title);
+    let parts = puzzle
+        .as_deref()
+        .map(extract_examples)
+        .filter(|parts| parts.first().is_some_and(|part| part.input.is_some()))
+        .unwrap_or_default();
+
+    let (contents, unknown) = templates::render(&template, &placeholders(day, title, &parts));
+    if !unknown.is_empty() {
+        eprintln!(
+            "Warning: unknown placeholder(s) in \"{}\": {}.",
+            template_path.display(),
+            unknown.join(", ")
+        );
+    }
 
     let mut file = match safe_create_file(&module_path, overwrite) {
         Ok(file) => file,
@@ -40,13 +122,13 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
+    match file.write_all(contents.as_bytes()) {
         Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+            println!(
+                "Created module file \"{}\" from template \"{}\"",
+                &module_path,
+                template_path.display()
+            );
         }
         Err(e) => {
             eprintln!("Failed to write module contents: {e}");
@@ -54,26 +136,40 @@ pub fn handle(day: Day, overwrite: bool) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    // NOTE: the input might already have been downloaded.
+    if !Path::new(&input_path).exists() {
+        match create_file(&input_path) {
+            Ok(_) => {
+                println!("Created empty input file \"{}\"", &input_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create input file: {e}");
+                process::exit(1);
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+    if !parts.is_empty() {
+        if let Err(e) = examples::write(day, &parts, overwrite) {
+            eprintln!("Failed to write example files: {e}");
             process::exit(1);
         }
+    } else {
+        match create_file(&example_path) {
+            Ok(_) => {
+                println!("Created empty example file \"{}\"", &example_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
+        }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    if year == Year::configured() {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
+    } else {
+        println!("🎄 Type `cargo solve {day} --year {year}` to run your solution.");
+    }
 }
@@ -1,9 +1,18 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, Stdio};
 
-use crate::template::Day;
+use crate::template::runner::OutputFormat;
+use crate::template::{Day, run_multi::get_bin_name};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    heap: bool,
+    submit_part: Option<u8>,
+    format: OutputFormat,
+    examples: bool,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), get_bin_name(day)];
 
     if dhat {
         cmd_args.extend([
@@ -16,6 +25,11 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push("--release".to_string());
     }
 
+    // NOTE: DHAT installs its own allocator, which takes precedence over the counting one.
+    if heap && !dhat {
+        cmd_args.extend(["--features".to_string(), "heap-stats".to_string()]);
+    }
+
     cmd_args.push("--".to_string());
 
     if let Some(submit_part) = submit_part {
@@ -23,6 +37,15 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if format == OutputFormat::Json {
+        cmd_args.push("--format".to_string());
+        cmd_args.push("json".to_string());
+    }
+
+    if examples {
+        cmd_args.push("--examples".to_string());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -30,5 +53,9 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    // NOTE: failing examples exit the solution with a non-zero code, which is passed on.
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
 }
@@ -1,19 +1,46 @@
 use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::time::Duration;
 
-use crate::template::run_multi::run_multi;
+use crate::template::history::{self, History, Run};
+use crate::template::registry::Entry;
+use crate::template::run_multi::{MultiOptions, run_multi};
+use crate::template::runner::{OutputFormat, RunOptions};
+use crate::template::stats::format_nanos;
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{ANSI_BOLD, ANSI_RESET, AllDays, Day, Year, readme_benchmarks};
+
+/// Options of the `time` command.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TimeOptions {
+    /// Bench days that already have a complete timing, too.
+    pub all: bool,
+    /// Store the timings, append them to the history and update the readme.
+    pub store: bool,
+    /// Compare the timings with the history, flagging steps that got slower by more than this many percent.
+    pub compare: Option<f64>,
+    pub format: OutputFormat,
+    pub timeout: Option<Duration>,
+}
+
+pub fn handle(registry: &[Entry], year: Year, day: Option<Day>, options: TimeOptions) {
+    let TimeOptions {
+        all: run_all,
+        store,
+        compare,
+        format,
+        timeout,
+    } = options;
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
     let stored_timings = Timings::read_from_file();
 
     let days_to_run = day.map_or_else(
         || {
             if run_all {
-                all_days().collect()
+                AllDays::for_year(year).collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
+                AllDays::for_year(year)
                     .filter(|day| !stored_timings.is_day_complete(*day))
                     .collect()
             }
@@ -21,15 +48,45 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = MultiOptions {
+        run: RunOptions {
+            bench: true,
+            format,
+            ..RunOptions::default()
+        },
+        // NOTE: benchmarks always run sequentially, so that days don't skew each other's timings.
+        jobs: NonZeroUsize::MIN,
+        timeout,
+    };
+
+    let timings = run_multi(registry, &days_to_run, options).unwrap();
+
+    if let Some(threshold) = compare {
+        if format == OutputFormat::Text {
+            println!();
+        }
+        print_comparison(
+            &history::compare(&History::read_from_file(), &timings),
+            threshold,
+            format,
+        );
+    }
 
     if store {
+        if let Err(e) = History::append(&Run::now(timings.clone())) {
+            eprintln!("Failed to append benchmarks to history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
-        println!();
         match readme_benchmarks::update(merged_timings) {
+            // NOTE: keep stdout machine-readable when printing JSON records.
+            Ok(()) if format == OutputFormat::Json => {
+                eprintln!("Stored updated benchmarks.");
+            }
             Ok(()) => {
+                println!();
                 println!("Stored updated benchmarks.");
             }
             Err(_) => {
@@ -38,3 +95,48 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Prints the change of every step since the previous run and flags regressions.
+/// NOTE: with `--format json`, the comparison goes to stderr to keep stdout machine-readable.
+fn print_comparison(changes: &[history::Change], threshold: f64, format: OutputFormat) {
+    let mut lines = vec![];
+
+    if changes.is_empty() {
+        lines.push("No previous runs to compare with, store a run with `--store` first.".into());
+    } else {
+        lines.push(format!("{ANSI_BOLD}Comparison{ANSI_RESET}"));
+        lines.push(String::new());
+        lines.push("| Day | Step | Since | Before | After | Change |".into());
+
+        for change in changes {
+            let mut cell = format!("{:+.1}%", change.percent());
+            if change.is_regression(threshold) {
+                cell.push_str(" ⚠ regression");
+            }
+
+            lines.push(format!(
+                "|  {} | {} | {} | {} | {} | {cell} |",
+                change.day,
+                change.step,
+                change.since,
+                format_nanos(change.before),
+                format_nanos(change.after),
+            ));
+        }
+
+        let regressions = changes
+            .iter()
+            .filter(|change| change.is_regression(threshold))
+            .count();
+        lines.push(String::new());
+        lines.push(format!("{regressions} regression(s) above {threshold}%."));
+    }
+
+    for line in lines {
+        if format == OutputFormat::Json {
+            eprintln!("{line}");
+        } else {
+            println!("{line}");
+        }
+    }
+}
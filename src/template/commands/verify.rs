@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::registry::{self, Entry};
+use crate::template::run_multi::{MultiOptions, run_days};
+use crate::template::{ANSI_BOLD, ANSI_RESET, AllDays, Day, Year};
+
+/// The result of comparing a part with its accepted answer.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail,
+    Unverified,
+}
+
+impl Status {
+    fn symbol(self) -> &'static str {
+        match self {
+            Status::Pass => "✔",
+            Status::Fail => "✖",
+            Status::Unverified => "·",
+        }
+    }
+}
+
+fn compare(accepted: Option<&str>, result: Option<&str>) -> Status {
+    match (accepted, result) {
+        (None, _) => Status::Unverified,
+        (Some(accepted), Some(result)) if accepted == result => Status::Pass,
+        (Some(_), _) => Status::Fail,
+    }
+}
+
+pub fn handle(registry: &[Entry], year: Year) {
+    let answers = Answers::read_from_file();
+
+    let days: HashSet<Day> = AllDays::for_year(year)
+        .filter(|day| registry::find(registry, *day).is_some())
+        .collect();
+
+    // NOTE: days that panicked or timed out have no result and fail if an answer was accepted.
+    let rows: Vec<(Day, [Status; 2])> = run_days(registry, &days, MultiOptions::default())
+        .into_iter()
+        .map(|(day, outcome)| {
+            let statuses = [1, 2].map(|part: u8| {
+                compare(
+                    answers.accepted(day, part),
+                    outcome
+                        .result()
+                        .and_then(|result| result.part(part))
+                        .map(|p| p.answer.as_str()),
+                )
+            });
+            (day, statuses)
+        })
+        .collect();
+
+    println!();
+    println!("{ANSI_BOLD}Verification {year}{ANSI_RESET}");
+    println!();
+    println!("| Day | Part 1 | Part 2 |");
+    for (day, [part_1, part_2]) in &rows {
+        println!(
+            "|  {day} |   {}    |   {}    |",
+            part_1.symbol(),
+            part_2.symbol()
+        );
+    }
+
+    let statuses = || rows.iter().flat_map(|(_, statuses)| statuses.iter());
+    let passed = statuses().filter(|s| **s == Status::Pass).count();
+    let failed = statuses().filter(|s| **s == Status::Fail).count();
+    let unverified = statuses().filter(|s| **s == Status::Unverified).count();
+
+    println!();
+    println!("{passed} passed, {failed} failed, {unverified} without accepted answer.");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
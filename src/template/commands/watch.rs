@@ -0,0 +1,290 @@
+/// Re-runs the tests and the solution of a day whenever its module, input or examples change.
+///
+/// Files are polled for their modification time, so this works without OS-specific file notifications.
+use std::collections::BTreeMap;
+use std::fs;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::thread;
+use std::time::{Duration, SystemTime};
+
+use tinyjson::JsonValue;
+
+use crate::template::examples;
+use crate::template::stats::format_nanos;
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day, run_multi::get_bin_name};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// The answer and median runtime of a part, read from a `--format json` record.
+#[derive(Clone, Debug, PartialEq)]
+struct PartAnswer {
+    answer: Option<String>,
+    nanos: Option<f64>,
+    /// The error of a part that failed.
+    message: Option<String>,
+}
+
+/// The module and input of a day, its examples manifest and every example file the manifest lists.
+/// NOTE: the manifest is read again on every poll, so files added to it are picked up.
+fn watched_paths(day: Day) -> Vec<String> {
+    let mut paths = vec![
+        format!("src/bin/{}.rs", get_bin_name(day)),
+        format!("data/{}/inputs/{day}.txt", day.year()),
+        examples::get_manifest_path(day),
+    ];
+    paths.extend(examples::get_file_paths(day));
+    paths
+}
+
+/// Modification times of the watched files, `None` for files that don't exist (yet).
+fn modified_times(paths: &[String]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| fs::metadata(path).and_then(|m| m.modified()).ok())
+        .collect()
+}
+
+pub fn handle(day: Day) {
+    let mut last_modified = None;
+    let mut previous: BTreeMap<u8, PartAnswer> = BTreeMap::new();
+
+    loop {
+        let paths = watched_paths(day);
+        let modified = (modified_times(&paths), paths);
+        if last_modified.as_ref() == Some(&modified) {
+            thread::sleep(POLL_INTERVAL);
+            continue;
+        }
+        let (_, paths) = last_modified.insert(modified);
+
+        print!("{ANSI_CLEAR}");
+        println!(
+            "{ANSI_ITALIC}Watching {}, press Ctrl+C to stop.{ANSI_RESET}",
+            paths.join(", ")
+        );
+        println!();
+
+        run_tests(day);
+
+        if let Some(answers) = run_solution(day) {
+            for (part, answer) in &answers {
+                println!("{}", format_answer(*part, answer, previous.get(part)));
+            }
+            previous = answers;
+        }
+    }
+}
+
+/// Runs the tests of a day, which include the generated check of its examples.
+fn run_tests(day: Day) {
+    println!("{ANSI_BOLD}Examples{ANSI_RESET}");
+
+    let status = Command::new("cargo")
+        .args(["test", "--quiet", "--bin", &get_bin_name(day)])
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .status();
+
+    if let Err(e) = status {
+        eprintln!("Failed to run tests: {e}");
+    }
+    println!();
+}
+
+/// Runs the solution of a day against the real input and collects the answers of its parts.
+fn run_solution(day: Day) -> Option<BTreeMap<u8, PartAnswer>> {
+    println!("{ANSI_BOLD}Input{ANSI_RESET}");
+
+    let output = Command::new("cargo")
+        .args([
+            "run",
+            "--quiet",
+            "--release",
+            "--bin",
+            &get_bin_name(day),
+            "--",
+            "--format",
+            "json",
+        ])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => {
+            Some(parse_answers(&String::from_utf8_lossy(&output.stdout)))
+        }
+        Ok(_) => {
+            println!("✖ the solution did not run successfully.");
+            None
+        }
+        Err(e) => {
+            eprintln!("Failed to run solution: {e}");
+            None
+        }
+    }
+}
+
+/// Reads the answers of all part records. Lines that are not records are ignored.
+fn parse_answers(stdout: &str) -> BTreeMap<u8, PartAnswer> {
+    let mut answers = BTreeMap::new();
+
+    for line in stdout.lines() {
+        let Ok(JsonValue::Object(record)) = JsonValue::from_str(line) else {
+            continue;
+        };
+
+        if record
+            .get("kind")
+            .and_then(|v| v.get::<String>())
+            .map(String::as_str)
+            != Some("part")
+        {
+            continue;
+        }
+
+        let Some(part) = record.get("part").and_then(|v| v.get::<f64>()) else {
+            continue;
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        answers.insert(
+            *part as u8,
+            PartAnswer {
+                answer: record
+                    .get("answer")
+                    .and_then(|v| v.get::<String>())
+                    .cloned(),
+                nanos: record.get("nanos").and_then(|v| v.get::<f64>()).copied(),
+                message: record
+                    .get("message")
+                    .and_then(|v| v.get::<String>())
+                    .cloned(),
+            },
+        );
+    }
+
+    answers
+}
+
+/// Formats the answer of a part and highlights whether it changed since the previous run.
+fn format_answer(part: u8, current: &PartAnswer, previous: Option<&PartAnswer>) -> String {
+    let answer = match (&current.answer, &current.message) {
+        (Some(answer), _) => answer.clone(),
+        (None, Some(message)) => format!("✖ {message}"),
+        (None, None) => "✖".into(),
+    };
+    let duration = current
+        .nanos
+        .map(|nanos| format!(" ({})", format_nanos(nanos)))
+        .unwrap_or_default();
+
+    let change = match previous {
+        None => String::new(),
+        Some(previous) if previous.answer == current.answer => {
+            format!(" {ANSI_ITALIC}unchanged{ANSI_RESET}")
+        }
+        Some(previous) => format!(
+            " {ANSI_BOLD}▲ changed{ANSI_RESET} (was {})",
+            previous.answer.as_deref().unwrap_or("✖")
+        ),
+    };
+
+    if answer.contains('\n') {
+        format!("Part {part}: ▼{duration}{change}\n{answer}")
+    } else {
+        format!("Part {part}: {ANSI_BOLD}{answer}{ANSI_RESET}{duration}{change}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartAnswer, format_answer, parse_answers};
+    use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+
+    fn answer(answer: &str) -> PartAnswer {
+        PartAnswer {
+            answer: Some(answer.into()),
+            nanos: None,
+            message: None,
+        }
+    }
+
+    #[test]
+    fn parses_part_records() {
+        let stdout = r#"Compiling day 01
+{"kind":"parse","status":"ok","nanos":10.0}
+{"kind":"part","part":1,"status":"ok","answer":"42","nanos":1200.0,"message":null}
+{"kind":"part","part":2,"status":"failed","answer":null,"message":"no path"}
+{"kind":"part","part":
+{"kind":"part","status":"ok","answer":"7"}
+[1, 2]
+{"kind":"day","status":"ok"}
+"#;
+
+        let answers = parse_answers(stdout);
+        assert_eq!(answers.len(), 2);
+        assert_eq!(
+            answers[&1],
+            PartAnswer {
+                answer: Some("42".into()),
+                nanos: Some(1200.0),
+                message: None,
+            }
+        );
+        assert_eq!(
+            answers[&2],
+            PartAnswer {
+                answer: None,
+                nanos: None,
+                message: Some("no path".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn ignores_output_without_records() {
+        assert!(parse_answers("").is_empty());
+        assert!(parse_answers("thread 'main' panicked\n{ invalid").is_empty());
+    }
+
+    #[test]
+    fn highlights_changed_answers() {
+        assert_eq!(
+            format_answer(1, &answer("42"), None),
+            format!("Part 1: {ANSI_BOLD}42{ANSI_RESET}")
+        );
+        assert_eq!(
+            format_answer(1, &answer("42"), Some(&answer("42"))),
+            format!("Part 1: {ANSI_BOLD}42{ANSI_RESET} {ANSI_ITALIC}unchanged{ANSI_RESET}")
+        );
+        assert_eq!(
+            format_answer(1, &answer("43"), Some(&answer("42"))),
+            format!("Part 1: {ANSI_BOLD}43{ANSI_RESET} {ANSI_BOLD}▲ changed{ANSI_RESET} (was 42)")
+        );
+    }
+
+    #[test]
+    fn formats_failed_and_multiline_answers() {
+        let failed = PartAnswer {
+            answer: None,
+            nanos: None,
+            message: Some("no path".into()),
+        };
+        assert_eq!(
+            format_answer(2, &failed, Some(&answer("42"))),
+            format!(
+                "Part 2: {ANSI_BOLD}✖ no path{ANSI_RESET} {ANSI_BOLD}▲ changed{ANSI_RESET} (was 42)"
+            )
+        );
+
+        assert_eq!(
+            format_answer(2, &answer("#.\n.#"), Some(&answer("#.\n.#"))),
+            format!("Part 2: ▼ {ANSI_ITALIC}unchanged{ANSI_RESET}\n#.\n.#")
+        );
+    }
+}
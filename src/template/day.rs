@@ -2,13 +2,17 @@ use std::error::Error;
 use std::fmt::Display;
 use std::str::FromStr;
 
+use crate::template::year::Year;
+
 #[cfg(feature = "today")]
 use chrono::{Datelike, FixedOffset, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
 
-/// A valid day number of advent (i.e. an integer in range 1 to 25).
+/// A valid day of advent in a given event year (i.e. an integer in range 1 to the last day of that year).
+///
+/// Events up to 2024 have 25 days, later events have 12. See [`Year::day_count`].
 ///
 /// # Display
 /// This value displays as a two digit number.
@@ -19,32 +23,54 @@ const SERVER_UTC_OFFSET: i32 = -5;
 /// assert_eq!(day.to_string(), "08")
 /// ```
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Day(u8);
+pub struct Day {
+    year: Year,
+    day: u8,
+}
 
 impl Day {
-    /// Creates a [`Day`] from the provided value if it's in the valid range,
+    /// Creates a [`Day`] of the configured year from the provided value if it's in the valid range,
     /// returns [`None`] otherwise.
     pub const fn new(day: u8) -> Option<Self> {
-        if day == 0 || day > 25 {
+        Self::new_in_year(day, Year::configured())
+    }
+
+    /// Creates a [`Day`] of `year` from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub const fn new_in_year(day: u8, year: Year) -> Option<Self> {
+        if day == 0 || day > year.day_count() {
             return None;
         }
-        Some(Self(day))
+        Some(Self { year, day })
+    }
+
+    /// Parses a [`Day`] of `year` from a day number.
+    pub fn from_str_in_year(s: &str, year: Year) -> Result<Self, DayFromStrError> {
+        let err = DayFromStrError { year };
+        let day = s.parse().map_err(|_| err)?;
+        Self::new_in_year(day, year).ok_or(err)
+    }
+
+    /// Returns the event year this [`Day`] belongs to.
+    pub fn year(self) -> Year {
+        self.year
     }
 
     /// Converts the [`Day`] into an [`u8`].
     pub fn into_inner(self) -> u8 {
-        self.0
+        self.day
     }
 }
 
 #[cfg(feature = "today")]
 impl Day {
-    /// Returns the current day if it's between the 1st and the 25th of december, `None` otherwise.
+    /// Returns the current day if it's a day of advent in the current year's calendar, `None` otherwise.
     pub fn today() -> Option<Self> {
         let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
         let today = Utc::now().with_timezone(&offset);
-        if today.month() == 12 && today.day() <= 25 {
-            Self::new(u8::try_from(today.day()).ok()?)
+        let year = Year::new(u16::try_from(today.year()).ok()?)?;
+        if today.month() == 12 {
+            Self::new_in_year(u8::try_from(today.day()).ok()?, year)
         } else {
             None
         }
@@ -53,19 +79,19 @@ impl Day {
 
 impl Display for Day {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:02}", self.0)
+        write!(f, "{:02}", self.day)
     }
 }
 
 impl PartialEq<u8> for Day {
     fn eq(&self, other: &u8) -> bool {
-        self.0.eq(other)
+        self.day.eq(other)
     }
 }
 
 impl PartialOrd<u8> for Day {
     fn partial_cmp(&self, other: &u8) -> Option<std::cmp::Ordering> {
-        self.0.partial_cmp(other)
+        self.day.partial_cmp(other)
     }
 }
 
@@ -75,39 +101,51 @@ impl FromStr for Day {
     type Err = DayFromStrError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let day = s.parse().map_err(|_| DayFromStrError)?;
-        Self::new(day).ok_or(DayFromStrError)
+        Self::from_str_in_year(s, Year::configured())
     }
 }
 
 /// An error which can be returned when parsing a [`Day`].
-#[derive(Debug)]
-pub struct DayFromStrError;
+#[derive(Debug, Clone, Copy)]
+pub struct DayFromStrError {
+    year: Year,
+}
 
 impl Error for DayFromStrError {}
 
 impl Display for DayFromStrError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.write_str("expecting a day number between 1 and 25")
+        write!(
+            f,
+            "expecting a day number between 1 and {} for {}",
+            self.year.day_count(),
+            self.year
+        )
     }
 }
 
 /* -------------------------------------------------------------------------- */
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent in the configured year's calendar.
 pub fn all_days() -> AllDays {
     AllDays::new()
 }
 
-/// An iterator that yields every day of advent from the 1st to the 25th.
+/// An iterator that yields every day of advent from the 1st to the last day of a year's calendar.
 pub struct AllDays {
+    year: Year,
     current: u8,
 }
 
 impl AllDays {
     #[allow(clippy::new_without_default)]
     pub fn new() -> Self {
-        Self { current: 1 }
+        Self::for_year(Year::configured())
+    }
+
+    /// Creates an iterator over the days of `year`'s calendar.
+    pub fn for_year(year: Year) -> Self {
+        Self { year, current: 1 }
     }
 }
 
@@ -115,11 +153,14 @@ impl Iterator for AllDays {
     type Item = Day;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.current > 25 {
+        if self.current > self.year.day_count() {
             return None;
         }
-        // NOTE: the iterator starts at 1 and we have verified that the value is not above 25.
-        let day = Day(self.current);
+        // NOTE: the iterator starts at 1 and we have verified that the value is not above the last day.
+        let day = Day {
+            year: self.year,
+            day: self.current,
+        };
         self.current += 1;
 
         Some(day)
@@ -129,12 +170,17 @@ impl Iterator for AllDays {
 /* -------------------------------------------------------------------------- */
 
 /// Creates a [`Day`] value in a const context.
+///
+/// Without a second argument, the day belongs to the configured year.
 #[macro_export]
 macro_rules! day {
     ($day:expr) => {
+        $crate::day!($day, $crate::template::Year::configured())
+    };
+    ($day:expr, $year:expr) => {
         const {
-            $crate::template::Day::new($day)
-                .expect("invalid day number, expecting a value between 1 and 25")
+            $crate::template::Day::new_in_year($day, $year)
+                .expect("invalid day number, expecting a value in the calendar of the given year")
         }
     };
 }
@@ -143,37 +189,66 @@ macro_rules! day {
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{Day, all_days};
+    use super::{AllDays, Day, all_days};
+    use crate::template::year::Year;
+
+    const fn day(year: u16, day: u8) -> Day {
+        Day {
+            year: Year::new(year).unwrap(),
+            day,
+        }
+    }
 
     #[test]
     fn all_days_iterator() {
-        let mut iter = all_days();
-
-        assert_eq!(iter.next(), Some(Day(1)));
-        assert_eq!(iter.next(), Some(Day(2)));
-        assert_eq!(iter.next(), Some(Day(3)));
-        assert_eq!(iter.next(), Some(Day(4)));
-        assert_eq!(iter.next(), Some(Day(5)));
-        assert_eq!(iter.next(), Some(Day(6)));
-        assert_eq!(iter.next(), Some(Day(7)));
-        assert_eq!(iter.next(), Some(Day(8)));
-        assert_eq!(iter.next(), Some(Day(9)));
-        assert_eq!(iter.next(), Some(Day(10)));
-        assert_eq!(iter.next(), Some(Day(11)));
-        assert_eq!(iter.next(), Some(Day(12)));
-        assert_eq!(iter.next(), Some(Day(13)));
-        assert_eq!(iter.next(), Some(Day(14)));
-        assert_eq!(iter.next(), Some(Day(15)));
-        assert_eq!(iter.next(), Some(Day(16)));
-        assert_eq!(iter.next(), Some(Day(17)));
-        assert_eq!(iter.next(), Some(Day(18)));
-        assert_eq!(iter.next(), Some(Day(19)));
-        assert_eq!(iter.next(), Some(Day(20)));
-        assert_eq!(iter.next(), Some(Day(21)));
-        assert_eq!(iter.next(), Some(Day(22)));
-        assert_eq!(iter.next(), Some(Day(23)));
-        assert_eq!(iter.next(), Some(Day(24)));
-        assert_eq!(iter.next(), Some(Day(25)));
+        assert_eq!(
+            all_days().count(),
+            usize::from(Year::configured().day_count())
+        );
+    }
+
+    #[test]
+    fn all_days_iterator_short_calendar() {
+        let days: Vec<Day> = AllDays::for_year(Year::new(2025).unwrap()).collect();
+        assert_eq!(days.len(), 12);
+        assert_eq!(days.first(), Some(&day(2025, 1)));
+        assert_eq!(days.last(), Some(&day(2025, 12)));
+    }
+
+    #[test]
+    fn new_in_year() {
+        let year = Year::new(2025).unwrap();
+        assert_eq!(Day::new_in_year(12, year), Some(day(2025, 12)));
+        assert_eq!(Day::new_in_year(13, year), None);
+        assert_eq!(Day::new_in_year(0, year), None);
+        assert_eq!(
+            Day::new_in_year(25, Year::new(2024).unwrap()),
+            Some(day(2024, 25))
+        );
+    }
+
+    #[test]
+    fn from_str_in_year() {
+        let year = Year::new(2023).unwrap();
+        assert_eq!(Day::from_str_in_year("7", year).unwrap(), day(2023, 7));
+        assert_eq!(Day::from_str_in_year("25", year).unwrap().year(), year);
+        assert!(Day::from_str_in_year("26", year).is_err());
+        assert!(Day::from_str_in_year("13", Year::new(2025).unwrap()).is_err());
+    }
+
+    #[test]
+    fn orders_by_year_first() {
+        assert!(day(2023, 25) < day(2024, 1));
+        assert!(day(2024, 1) < day(2024, 2));
+    }
+
+    #[test]
+    fn all_days_iterator_full_calendar() {
+        let mut iter = AllDays::for_year(Year::new(2024).unwrap());
+
+        for n in 1..=25 {
+            assert_eq!(iter.next(), Some(day(2024, n)));
+        }
         assert_eq!(iter.next(), None);
     }
 }
@@ -0,0 +1,745 @@
+/// Example inputs with expected answers, declared in a manifest per day, e.g. `data/2023/examples/07.toml`:
+///
+/// ```toml
+/// [[example]]
+/// file = "07.txt"
+/// part_one = 6440
+/// part_two = 5905
+///
+/// [[example]]
+/// name = "joker edge case"
+/// input = """
+/// JJJJJ 1
+/// """
+/// part_two = 1
+/// ```
+///
+/// An example either reads its input from a `file` in the examples folder or declares it inline as `input`.
+/// Expected answers are optional, parts without one are not checked.
+///
+/// Manifests are parsed as a strict subset of TOML. Anything outside of it is an error with the line it occurs on:
+///
+/// - Blank lines and comments, from `#` outside of a string to the end of the line.
+/// - `[[example]]` headers, which start the next example. Other tables are not supported.
+/// - `key = value` pairs of the keys `name`, `input`, `file`, `part_one` and `part_two`, each at most once
+///   per example. Dotted and quoted keys are not supported.
+/// - Basic strings `"…"` with the escapes `\n`, `\t`, `\r`, `\"` and `\\`, literal strings `'…'`, and their
+///   multi-line forms `"""…"""` and `'''…'''`. A newline directly after the opening delimiter is trimmed.
+/// - Decimal integers with an optional sign and `_` between digits, e.g. `1_000`, for `part_one` and `part_two`.
+///
+/// Other values, e.g. floats, booleans, arrays and inline tables, are not supported.
+use std::error::Error;
+use std::fmt::Display;
+use std::{fs, io};
+
+use crate::template::output::outln;
+use crate::template::puzzle::PartExample;
+use crate::template::solution::Answer;
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day};
+
+/// An example input with the expected answers of its parts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub part_one: Option<String>,
+    pub part_two: Option<String>,
+}
+
+impl Example {
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_one.as_deref(),
+            _ => self.part_two.as_deref(),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExamplesError {
+    IO(io::Error),
+    Parse { line: usize, message: String },
+}
+
+impl Display for ExamplesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExamplesError::IO(e) => write!(f, "could not read examples: {e}"),
+            ExamplesError::Parse { line, message } => {
+                write!(f, "invalid examples manifest, line {line}: {message}")
+            }
+        }
+    }
+}
+
+impl Error for ExamplesError {}
+
+impl From<io::Error> for ExamplesError {
+    fn from(e: io::Error) -> Self {
+        ExamplesError::IO(e)
+    }
+}
+
+/// Returns the path of the examples manifest of a day.
+#[must_use]
+pub fn get_manifest_path(day: Day) -> String {
+    format!("data/{}/examples/{day}.toml", day.year())
+}
+
+fn get_file_path(day: Day, file: &str) -> String {
+    format!("data/{}/examples/{file}", day.year())
+}
+
+/// Returns the paths of the example files listed in the manifest of a day.
+/// Returns no paths if the manifest is missing or invalid.
+#[must_use]
+pub fn get_file_paths(day: Day) -> Vec<String> {
+    let Ok(manifest) = fs::read_to_string(get_manifest_path(day)) else {
+        return vec![];
+    };
+
+    parse_manifest(&manifest)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|table| match table.source {
+            Source::File(file) => Some(get_file_path(day, &file)),
+            Source::Inline(_) => None,
+        })
+        .collect()
+}
+
+/// Reads the examples of a day. Returns no examples if the day has no manifest.
+pub fn read(day: Day) -> Result<Vec<Example>, ExamplesError> {
+    let manifest = match fs::read_to_string(get_manifest_path(day)) {
+        Ok(manifest) => manifest,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(vec![]),
+        Err(e) => return Err(e.into()),
+    };
+
+    parse_manifest(&manifest)?
+        .into_iter()
+        .map(|table| {
+            let input = match table.source {
+                Source::Inline(input) => input,
+                Source::File(file) => fs::read_to_string(get_file_path(day, &file))?,
+            };
+
+            Ok(Example {
+                name: table.name,
+                input,
+                part_one: table.part_one,
+                part_two: table.part_two,
+            })
+        })
+        .collect()
+}
+
+/// Proposes example files and a manifest for the examples extracted from the puzzle description.
+/// Returns pairs of path and contents. Part two gets its own file `<day>-2.txt` if its example differs.
+#[must_use]
+pub fn propose_files(day: Day, parts: &[PartExample]) -> Vec<(String, String)> {
+    let folder = format!("data/{}/examples", day.year());
+    let empty = PartExample::default();
+    let one = parts.first().unwrap_or(&empty);
+    let two = parts.get(1).unwrap_or(&empty);
+
+    let shares_input = two.input.is_none() || two.input == one.input;
+
+    let mut files = vec![];
+    let mut manifest = String::new();
+
+    if let Some(input) = &one.input {
+        let part_two = if shares_input { &two.answer } else { &None };
+        files.push((format!("{folder}/{day}.txt"), input.clone()));
+        manifest.push_str(&format_table(&format!("{day}.txt"), &one.answer, part_two));
+    }
+
+    if let Some(input) = two.input.as_ref().filter(|_| !shares_input) {
+        if !manifest.is_empty() {
+            manifest.push('\n');
+        }
+        files.push((format!("{folder}/{day}-2.txt"), input.clone()));
+        manifest.push_str(&format_table(&format!("{day}-2.txt"), &None, &two.answer));
+    }
+
+    if !manifest.is_empty() {
+        files.push((get_manifest_path(day), manifest));
+    }
+
+    files
+}
+
+fn format_table(file: &str, part_one: &Option<String>, part_two: &Option<String>) -> String {
+    let mut table = format!("[[example]]\nfile = {}\n", format_value(file));
+    if let Some(answer) = part_one {
+        table.push_str(&format!("part_one = {}\n", format_value(answer)));
+    }
+    if let Some(answer) = part_two {
+        table.push_str(&format!("part_two = {}\n", format_value(answer)));
+    }
+    table
+}
+
+/// Formats an answer as an integer if it reads back unchanged, and as a basic string otherwise.
+fn format_value(value: &str) -> String {
+    if parse_integer(value).as_deref() == Some(value) {
+        return value.to_string();
+    }
+
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n");
+    format!("\"{escaped}\"")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The outcome of running a part on an example.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Check {
+    Pass,
+    Fail { expected: String, actual: Answer },
+}
+
+/// Runs every example with an expected answer for a part that is implemented.
+/// `solve` returns `None` for parts that are not implemented, and the answer of the part otherwise.
+pub fn check(
+    examples: &[Example],
+    solve: impl Fn(&str, u8) -> Option<Answer>,
+) -> Vec<(&Example, u8, Check)> {
+    let mut checks = vec![];
+
+    for example in examples {
+        for part in [1, 2] {
+            let Some(expected) = example.expected(part) else {
+                continue;
+            };
+
+            let Some(actual) = solve(&example.input, part) else {
+                continue;
+            };
+
+            let check = if actual.value().as_deref() == Some(expected) {
+                Check::Pass
+            } else {
+                Check::Fail {
+                    expected: expected.to_string(),
+                    actual,
+                }
+            };
+
+            checks.push((example, part, check));
+        }
+    }
+
+    checks
+}
+
+fn describe(example: &Example, part: u8, check: &Check) -> String {
+    match check {
+        Check::Pass => format!("{} part {part}: ✔", example.name),
+        Check::Fail { expected, actual } => format!(
+            "{} part {part}: ✖ got {actual}, expected {expected}",
+            example.name,
+        ),
+    }
+}
+
+/// Runs the examples of a day before the real input, used by `cargo solve <day> --examples`.
+/// Returns whether all examples passed.
+pub fn run(day: Day, solve: impl Fn(&str, u8) -> Option<Answer>) -> bool {
+    let examples = match read(day) {
+        Ok(examples) => examples,
+        Err(e) => {
+            eprintln!("{e}");
+            return false;
+        }
+    };
+
+    if examples.is_empty() {
+        outln!("No examples found at \"{}\".", get_manifest_path(day));
+        outln!();
+        return true;
+    }
+
+    outln!("{ANSI_BOLD}Examples{ANSI_RESET}");
+
+    let checks = check(&examples, solve);
+    for (example, part, check) in &checks {
+        outln!("{}", describe(example, *part, check));
+    }
+    outln!();
+
+    checks.iter().all(|(_, _, check)| *check == Check::Pass)
+}
+
+/// Asserts that all examples of a day pass, used by the test that `solution!` generates.
+pub fn assert_all(day: Day, solve: impl Fn(&str, u8) -> Option<Answer>) {
+    let examples = read(day).unwrap_or_else(|e| panic!("{e}"));
+
+    let failures: Vec<String> = check(&examples, solve)
+        .iter()
+        .filter(|(_, _, check)| *check != Check::Pass)
+        .map(|(example, part, check)| describe(example, *part, check))
+        .collect();
+
+    assert!(
+        failures.is_empty(),
+        "examples failed:\n{}",
+        failures.join("\n")
+    );
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Where the input of an example comes from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Source {
+    Inline(String),
+    File(String),
+}
+
+/// An `[[example]]` table of the manifest.
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Table {
+    name: String,
+    source: Source,
+    part_one: Option<String>,
+    part_two: Option<String>,
+}
+
+#[derive(Default)]
+struct PartialTable {
+    line: usize,
+    name: Option<String>,
+    input: Option<String>,
+    file: Option<String>,
+    part_one: Option<String>,
+    part_two: Option<String>,
+}
+
+impl PartialTable {
+    fn finish(self, index: usize) -> Result<Table, ExamplesError> {
+        let source = match (self.input, self.file) {
+            (Some(input), None) => Source::Inline(input),
+            (None, Some(file)) => Source::File(file),
+            _ => {
+                return Err(ExamplesError::Parse {
+                    line: self.line,
+                    message: "expected an example to have either `input` or `file`.".into(),
+                });
+            }
+        };
+
+        Ok(Table {
+            name: self
+                .name
+                .unwrap_or_else(|| format!("Example {}", index + 1)),
+            source,
+            part_one: self.part_one,
+            part_two: self.part_two,
+        })
+    }
+}
+
+/// Parses the subset of TOML used by manifests, see the module documentation.
+fn parse_manifest(s: &str) -> Result<Vec<Table>, ExamplesError> {
+    let mut tables: Vec<PartialTable> = vec![];
+    let mut lines = s.lines().enumerate().map(|(i, line)| (i + 1, line));
+
+    while let Some((line_number, line)) = lines.next() {
+        let error = |message: &str| ExamplesError::Parse {
+            line: line_number,
+            message: message.into(),
+        };
+
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        if strip_comment(line) == "[[example]]" {
+            tables.push(PartialTable {
+                line: line_number,
+                ..PartialTable::default()
+            });
+            continue;
+        }
+
+        if line.starts_with('[') {
+            return Err(error(&format!(
+                "unsupported table `{}`, expected `[[example]]`.",
+                strip_comment(line)
+            )));
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| error("expected `[[example]]` or `key = value`."))?;
+        let key = key.trim();
+
+        let table = tables
+            .last_mut()
+            .ok_or_else(|| error("expected `[[example]]` before the first key."))?;
+
+        let (slot, is_answer) = match key {
+            "name" => (&mut table.name, false),
+            "input" => (&mut table.input, false),
+            "file" => (&mut table.file, false),
+            "part_one" => (&mut table.part_one, true),
+            "part_two" => (&mut table.part_two, true),
+            key => return Err(error(&format!("unknown key `{key}`."))),
+        };
+
+        let value = match parse_value(value.trim(), &mut lines).map_err(|m| error(&m))? {
+            Value::String(value) => value,
+            Value::Integer(value) if is_answer => value,
+            Value::Integer(_) => return Err(error(&format!("expected a string for `{key}`."))),
+        };
+
+        if slot.replace(value).is_some() {
+            return Err(error(&format!("duplicate key `{key}`.")));
+        }
+    }
+
+    tables
+        .into_iter()
+        .enumerate()
+        .map(|(i, table)| table.finish(i))
+        .collect()
+}
+
+/// A value of the manifest. Integers are kept in their normalized decimal form.
+enum Value {
+    String(String),
+    Integer(String),
+}
+
+/// Parses a string or integer value. Multi-line strings consume the following lines.
+fn parse_value<'a>(
+    value: &str,
+    lines: &mut impl Iterator<Item = (usize, &'a str)>,
+) -> Result<Value, String> {
+    for delimiter in ["\"\"\"", "'''"] {
+        let Some(rest) = value.strip_prefix(delimiter) else {
+            continue;
+        };
+
+        let is_literal = delimiter == "'''";
+
+        // NOTE: a newline directly after the opening delimiter is not part of the string.
+        let mut raw = String::from(rest);
+        let mut is_first_line = rest.is_empty();
+
+        loop {
+            if let Some(end) = raw.find(delimiter) {
+                if !strip_comment(&raw[end + 3..]).is_empty() {
+                    return Err("unexpected characters after string.".into());
+                }
+                raw.truncate(end);
+                break;
+            }
+
+            let (_, line) = lines
+                .next()
+                .ok_or_else(|| format!("unterminated string, expected closing {delimiter}."))?;
+
+            if !is_first_line {
+                raw.push('\n');
+            }
+            is_first_line = false;
+            raw.push_str(line);
+        }
+
+        return if is_literal {
+            Ok(Value::String(raw))
+        } else {
+            unescape(&raw).map(Value::String)
+        };
+    }
+
+    if let Some(rest) = value.strip_prefix('"') {
+        let end = find_closing_quote(rest).ok_or("unterminated string, expected closing \".")?;
+        if !strip_comment(&rest[end + 1..]).is_empty() {
+            return Err("unexpected characters after string.".into());
+        }
+        return unescape(&rest[..end]).map(Value::String);
+    }
+
+    if let Some(rest) = value.strip_prefix('\'') {
+        let end = rest
+            .find('\'')
+            .ok_or("unterminated string, expected closing '.")?;
+        if !strip_comment(&rest[end + 1..]).is_empty() {
+            return Err("unexpected characters after string.".into());
+        }
+        return Ok(Value::String(rest[..end].to_string()));
+    }
+
+    let value = strip_comment(value);
+    parse_integer(value)
+        .map(Value::Integer)
+        .ok_or_else(|| format!("expected a string or an integer, got `{value}`."))
+}
+
+/// Parses a decimal integer. Underscores must be between digits and leading zeros are not allowed.
+fn parse_integer(value: &str) -> Option<String> {
+    let digits = value.strip_prefix(['+', '-']).unwrap_or(value);
+
+    let is_valid = digits.starts_with(|c: char| c.is_ascii_digit())
+        && digits.ends_with(|c: char| c.is_ascii_digit())
+        && digits.chars().all(|c| c.is_ascii_digit() || c == '_')
+        && !digits.contains("__")
+        && (digits == "0" || !digits.starts_with('0'));
+
+    if !is_valid {
+        return None;
+    }
+
+    value
+        .replace('_', "")
+        .parse::<i128>()
+        .ok()
+        .map(|n| n.to_string())
+}
+
+/// Finds the closing quote of a basic string, skipping escaped quotes.
+fn find_closing_quote(s: &str) -> Option<usize> {
+    let mut is_escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            '\\' if !is_escaped => is_escaped = true,
+            '"' if !is_escaped => return Some(i),
+            _ => is_escaped = false,
+        }
+    }
+    None
+}
+
+fn strip_comment(s: &str) -> &str {
+    s.split('#').next().unwrap_or_default().trim()
+}
+
+fn unescape(s: &str) -> Result<String, String> {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            out.push(c);
+            continue;
+        }
+
+        match chars.next() {
+            Some('n') => out.push('\n'),
+            Some('t') => out.push('\t'),
+            Some('r') => out.push('\r'),
+            Some('"') => out.push('"'),
+            Some('\\') => out.push('\\'),
+            Some('\n') => return Err("line ending backslashes are not supported.".into()),
+            Some(c) => return Err(format!("unsupported escape sequence `\\{c}`.")),
+            None => return Err("unexpected end of string after `\\`.".into()),
+        }
+    }
+
+    Ok(out)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Check, Example, ExamplesError, Source, check, parse_manifest, propose_files};
+    use crate::template::puzzle::PartExample;
+    use crate::template::solution::Answer;
+    use crate::{day, year};
+
+    #[test]
+    fn parses_manifest() {
+        let manifest = r#"
+# examples of day 7
+[[example]]
+file = "07.txt"
+part_one = 6440 # from the puzzle text
+part_two = "5905"
+
+[[example]]
+name = "inline"
+input = """
+32T3K 765
+T55J5 684"""
+part_two = 1_000
+"#;
+
+        let tables = parse_manifest(manifest).unwrap();
+        assert_eq!(tables.len(), 2);
+
+        assert_eq!(tables[0].name, "Example 1");
+        assert_eq!(tables[0].source, Source::File("07.txt".into()));
+        assert_eq!(tables[0].part_one.as_deref(), Some("6440"));
+        assert_eq!(tables[0].part_two.as_deref(), Some("5905"));
+
+        assert_eq!(tables[1].name, "inline");
+        assert_eq!(
+            tables[1].source,
+            Source::Inline("32T3K 765\nT55J5 684".into())
+        );
+        assert_eq!(tables[1].part_one, None);
+        assert_eq!(tables[1].part_two.as_deref(), Some("1000"));
+    }
+
+    #[test]
+    fn parses_strings() {
+        let manifest = "[[example]]\ninput = '''\n\\d+ # not a comment\n'''\npart_one = \"a \\\"quoted\\\" # answer\"\n";
+        let tables = parse_manifest(manifest).unwrap();
+        assert_eq!(
+            tables[0].source,
+            Source::Inline("\\d+ # not a comment\n".into())
+        );
+        assert_eq!(tables[0].part_one.as_deref(), Some("a \"quoted\" # answer"));
+    }
+
+    #[test]
+    fn reports_errors_with_line() {
+        let error = |manifest: &str| match parse_manifest(manifest) {
+            Err(ExamplesError::Parse { line, message }) => (line, message),
+            _ => panic!("expected a parse error"),
+        };
+
+        assert_eq!(error("part_one = 1").0, 1);
+        assert_eq!(error("[[example]]\nfile = \"a.txt\"\ncolor = 1").0, 3);
+        assert_eq!(
+            error("[[example]]\nfile = \"a.txt\"\nfile = \"b.txt\"").0,
+            3
+        );
+        assert_eq!(error("[[example]]\ninput = \"\"\"\nabc").0, 2);
+        assert!(
+            error("\n[[example]]\npart_one = 1")
+                .1
+                .contains("`input` or `file`")
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_toml() {
+        let error = |manifest: &str| match parse_manifest(&format!("[[example]]\n{manifest}")) {
+            Err(ExamplesError::Parse { message, .. }) => message,
+            _ => panic!("expected a parse error for {manifest:?}"),
+        };
+
+        assert_eq!(
+            error("[example.input] # table"),
+            "unsupported table `[example.input]`, expected `[[example]]`."
+        );
+        assert_eq!(
+            error("example.file = \"a.txt\""),
+            "unknown key `example.file`."
+        );
+        assert_eq!(error("\"file\" = \"a.txt\""), "unknown key `\"file\"`.");
+        assert_eq!(error("file = 7"), "expected a string for `file`.");
+
+        for value in [
+            "1.5",
+            "true",
+            "[1, 2]",
+            "{ a = 1 }",
+            "0x1f",
+            "007",
+            "1__0",
+            "_1",
+            "1_",
+        ] {
+            assert_eq!(
+                error(&format!("part_one = {value}")),
+                format!("expected a string or an integer, got `{value}`.")
+            );
+        }
+
+        assert_eq!(
+            error("part_one = \"\\u0041\""),
+            "unsupported escape sequence `\\u`."
+        );
+        assert_eq!(
+            error("input = \"\"\"\nabc \\\ndef\"\"\""),
+            "line ending backslashes are not supported."
+        );
+    }
+
+    #[test]
+    fn parses_integers() {
+        let manifest = "[[example]]\nfile = \"a.txt\"\npart_one = -1_000\npart_two = +0\n";
+        let tables = parse_manifest(manifest).unwrap();
+        assert_eq!(tables[0].part_one.as_deref(), Some("-1000"));
+        assert_eq!(tables[0].part_two.as_deref(), Some("0"));
+    }
+
+    #[test]
+    fn checks_examples() {
+        let examples = [Example {
+            name: "Example 1".into(),
+            input: "1 2 3".into(),
+            part_one: Some("6".into()),
+            part_two: Some("7".into()),
+        }];
+
+        let sum =
+            |input: &str| -> i128 { input.split(' ').map(|x| x.parse::<i128>().unwrap()).sum() };
+
+        let checks = check(&examples, |input, part| match part {
+            1 => Some(Answer::Number(sum(input))),
+            _ => Some(Answer::Unsolved),
+        });
+
+        assert_eq!(checks.len(), 2);
+        assert_eq!(checks[0].2, Check::Pass);
+        assert_eq!(
+            checks[1].2,
+            Check::Fail {
+                expected: "7".into(),
+                actual: Answer::Unsolved
+            }
+        );
+
+        // parts that are not implemented are skipped.
+        assert_eq!(check(&examples, |_, _| None).len(), 0);
+    }
+
+    #[test]
+    fn proposes_files() {
+        let day = day!(7, year!(2023));
+        let part = |input: &str, answer: &str| PartExample {
+            input: Some(input.into()),
+            answer: Some(answer.into()),
+        };
+
+        let files = propose_files(day, &[part("a\n", "6440"), part("a\n", "x\"y")]);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0], ("data/2023/examples/07.txt".into(), "a\n".into()));
+        assert_eq!(files[1].0, "data/2023/examples/07.toml");
+
+        let tables = parse_manifest(&files[1].1).unwrap();
+        assert_eq!(tables.len(), 1);
+        assert_eq!(tables[0].source, Source::File("07.txt".into()));
+        assert_eq!(tables[0].part_one.as_deref(), Some("6440"));
+        assert_eq!(tables[0].part_two.as_deref(), Some("x\"y"));
+
+        let files = propose_files(day, &[part("a\n", "007")]);
+        let tables = parse_manifest(&files[1].1).unwrap();
+        assert_eq!(tables[0].part_one.as_deref(), Some("007"));
+
+        let files = propose_files(day, &[part("a\n", "1"), part("b\n", "2")]);
+        assert_eq!(files.len(), 3);
+        assert_eq!(
+            files[1],
+            ("data/2023/examples/07-2.txt".into(), "b\n".into())
+        );
+
+        let tables = parse_manifest(&files[2].1).unwrap();
+        assert_eq!(tables[1].source, Source::File("07-2.txt".into()));
+        assert_eq!(tables[1].part_one, None);
+        assert_eq!(tables[1].part_two.as_deref(), Some("2"));
+
+        assert!(propose_files(day, &[PartExample::default()]).is_empty());
+    }
+}
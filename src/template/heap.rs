@@ -0,0 +1,198 @@
+//! Heap statistics of solution parts, collected by a counting global allocator.
+//!
+//! The allocator is installed with the `heap-stats` feature, unless `dhat-heap` installs its own.
+//! NOTE: the counters are global, so steps that run in parallel, e.g. with `cargo all --jobs`, skew each other's statistics.
+
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicU64, Ordering};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static CURRENT: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+/// A global allocator that counts allocations and tracks the peak heap size.
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+pub struct CountingAlloc;
+
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+#[global_allocator]
+static ALLOC: CountingAlloc = CountingAlloc;
+
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+fn record_alloc(size: usize) {
+    let size = size as u64;
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+    let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+    PEAK.fetch_max(current, Ordering::Relaxed);
+}
+
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+fn record_dealloc(size: usize) {
+    CURRENT.fetch_sub(size as u64, Ordering::Relaxed);
+}
+
+#[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+// SAFETY: all allocations are delegated to the system allocator, counting has no effect on them.
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = unsafe { System.alloc_zeroed(layout) };
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) };
+        record_dealloc(layout.size());
+    }
+
+    /// Counts a reallocation as a new allocation, the same way DHAT does.
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = unsafe { System.realloc(ptr, layout, new_size) };
+        if !new_ptr.is_null() {
+            record_dealloc(layout.size());
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Heap usage of a step of a solution.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct HeapStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: u64,
+    /// Total number of bytes allocated.
+    pub bytes: u64,
+    /// Maximum number of bytes the step held on the heap at once.
+    pub peak: u64,
+}
+
+impl HeapStats {
+    /// Combines the statistics of the steps of a day: allocations add up, the peak is the highest of all steps.
+    #[must_use]
+    pub fn combine(stats: impl IntoIterator<Item = HeapStats>) -> Option<HeapStats> {
+        stats.into_iter().reduce(|a, b| HeapStats {
+            allocations: a.allocations + b.allocations,
+            bytes: a.bytes + b.bytes,
+            peak: a.peak.max(b.peak),
+        })
+    }
+}
+
+/// Whether the counting allocator is installed.
+#[must_use]
+pub const fn is_enabled() -> bool {
+    cfg!(all(feature = "heap-stats", not(feature = "dhat-heap")))
+}
+
+/// Runs a function and returns the heap statistics of that run, if the counting allocator is installed.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, Option<HeapStats>) {
+    if !is_enabled() {
+        return (func(), None);
+    }
+
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed);
+    let bytes = BYTES.load(Ordering::Relaxed);
+    let baseline = CURRENT.load(Ordering::Relaxed);
+    PEAK.store(baseline, Ordering::Relaxed);
+
+    let result = func();
+
+    let stats = HeapStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed) - allocations,
+        bytes: BYTES.load(Ordering::Relaxed) - bytes,
+        peak: PEAK.load(Ordering::Relaxed).saturating_sub(baseline),
+    };
+
+    (result, Some(stats))
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[must_use]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{HeapStats, format_bytes};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn combines_stats() {
+        let a = HeapStats {
+            allocations: 2,
+            bytes: 100,
+            peak: 80,
+        };
+        let b = HeapStats {
+            allocations: 3,
+            bytes: 50,
+            peak: 120,
+        };
+
+        assert_eq!(
+            HeapStats::combine([a, b]),
+            Some(HeapStats {
+                allocations: 5,
+                bytes: 150,
+                peak: 120
+            })
+        );
+        assert_eq!(HeapStats::combine([]), None);
+    }
+
+    #[cfg(all(feature = "heap-stats", not(feature = "dhat-heap")))]
+    #[test]
+    fn measures_allocations() {
+        let (_, stats) = super::measure(|| {
+            let small = vec![0_u8; 1000];
+            drop(small);
+            vec![0_u8; 4000]
+        });
+
+        let stats = stats.unwrap();
+        // NOTE: the test harness allocates on other threads, so only check lower bounds.
+        assert!(stats.allocations >= 2);
+        assert!(stats.bytes >= 5000);
+        assert!(stats.peak >= 4000);
+    }
+}
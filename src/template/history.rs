@@ -0,0 +1,359 @@
+//! Append-only history of stored benchmark runs, one JSON object per line in `data/timings_history.jsonl`.
+//!
+//! Unlike `timings.json`, which only holds the latest timing of every day, the history keeps every run
+//! together with the git commit it was measured on, so that runs can be compared over time.
+
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::{Error, Write};
+use std::process::Command;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+use crate::template::timings::{Timing, Timings};
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// The timings of a single benchmark run.
+#[derive(Clone, Debug)]
+pub struct Run {
+    /// The commit the run was measured on, e.g. `a1b2c3d` or `a1b2c3d-dirty`. `None` outside of a git repository.
+    pub commit: Option<String>,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    pub timings: Timings,
+}
+
+impl Run {
+    /// Creates a run measured on the current commit, now.
+    pub fn now(timings: Timings) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+
+        Self {
+            commit: current_commit(),
+            timestamp,
+            timings,
+        }
+    }
+
+    /// Describes the run, e.g. `a1b2c3d` or `@1701388800` if there is no commit.
+    pub fn label(&self) -> String {
+        match &self.commit {
+            Some(commit) => commit.clone(),
+            None => format!("@{}", self.timestamp),
+        }
+    }
+}
+
+/// Every stored benchmark run, oldest first.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub runs: Vec<Run>,
+}
+
+impl History {
+    /// Appends a run to the history file.
+    pub fn append(run: &Run) -> Result<(), Error> {
+        // NOTE: serializing an object of plain values can not fail.
+        let line = JsonValue::from(run).stringify().unwrap();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(HISTORY_FILE_PATH)?;
+        writeln!(file, "{line}")
+    }
+
+    /// Rehydrate the history from its file. If not present, returns an empty history.
+    /// Lines that can't be read are skipped, so a single corrupt line doesn't lose the history.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(HISTORY_FILE_PATH)
+            .map(|s| Self::parse(&s))
+            .unwrap_or_default()
+    }
+
+    fn parse(s: &str) -> Self {
+        let runs = s
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| Run::try_from(line).ok())
+            .collect();
+
+        Self { runs }
+    }
+
+    /// Returns the latest run that timed a day, together with its timing.
+    pub fn latest(&self, day: Day) -> Option<(&Run, &Timing)> {
+        self.runs.iter().rev().find_map(|run| {
+            run.timings
+                .data
+                .iter()
+                .find(|timing| timing.day == day)
+                .map(|timing| (run, timing))
+        })
+    }
+}
+
+/// Returns `git describe --always --dirty` of the working directory.
+fn current_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["describe", "--always", "--dirty"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let commit = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!commit.is_empty()).then_some(commit)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The change of the median time of a step between a stored run and the current run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub day: Day,
+    /// The step that changed, e.g. `Part 1` or `Total`.
+    pub step: &'static str,
+    /// The run that is compared against.
+    pub since: String,
+    pub before: f64,
+    pub after: f64,
+}
+
+impl Change {
+    /// The relative change in percent, positive if the step got slower.
+    pub fn percent(&self) -> f64 {
+        if self.before == 0.0 {
+            return 0.0;
+        }
+        (self.after - self.before) / self.before * 100.0
+    }
+
+    /// Whether the step got slower by more than `threshold` percent.
+    pub fn is_regression(&self, threshold: f64) -> bool {
+        self.percent() > threshold
+    }
+}
+
+/// Compares every day of `timings` with the latest run of the history that timed it.
+/// Steps that only one of the runs timed are skipped, and the total is only compared when both runs timed
+/// the same steps, since it would otherwise compare different amounts of work.
+pub fn compare(history: &History, timings: &Timings) -> Vec<Change> {
+    let mut changes = vec![];
+
+    for timing in &timings.data {
+        let Some((run, previous)) = history.latest(timing.day) else {
+            continue;
+        };
+
+        let steps = [
+            ("Parse", &previous.parse, &timing.parse),
+            ("Part 1", &previous.part_1, &timing.part_1),
+            ("Part 2", &previous.part_2, &timing.part_2),
+        ];
+
+        let same_steps = steps
+            .iter()
+            .all(|(_, before, after)| before.is_some() == after.is_some());
+
+        for (step, before, after) in steps {
+            if let (Some(before), Some(after)) = (before, after) {
+                changes.push(Change {
+                    day: timing.day,
+                    step,
+                    since: run.label(),
+                    before: before.median,
+                    after: after.median,
+                });
+            }
+        }
+
+        if same_steps {
+            changes.push(Change {
+                day: timing.day,
+                step: "Total",
+                since: run.label(),
+                before: previous.total_nanos,
+                after: timing.total_nanos,
+            });
+        }
+    }
+
+    changes
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Run> for JsonValue {
+    fn from(value: &Run) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .as_ref()
+                .map_or(JsonValue::Null, |commit| JsonValue::String(commit.clone())),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for Run {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected run to be an object.")?;
+
+        let commit = json.get("commit").and_then(|v| v.get::<String>()).cloned();
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|timestamp| *timestamp as u64)
+            .ok_or("expected run.timestamp to be a number.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("expected run.data to be an array.")?
+            .iter()
+            .map(Timing::try_from)
+            .collect::<Result<_, _>>()?;
+
+        Ok(Run {
+            commit,
+            timestamp,
+            timings: Timings { data },
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{History, Run, compare};
+    use crate::day;
+    use crate::template::Day;
+    use crate::template::stats::Stats;
+    use crate::template::timings::{Timing, Timings};
+
+    fn timing(day: u8, part_1: f64, part_2: Option<f64>) -> Timing {
+        Timing {
+            day: Day::new(day).unwrap(),
+            parse: None,
+            part_1: Some(Stats::single(part_1)),
+            part_2: part_2.map(Stats::single),
+            total_nanos: part_1 + part_2.unwrap_or_default(),
+            heap: None,
+        }
+    }
+
+    fn run(commit: &str, data: Vec<Timing>) -> Run {
+        Run {
+            commit: Some(commit.into()),
+            timestamp: 1_701_388_800,
+            timings: Timings { data },
+        }
+    }
+
+    #[test]
+    fn roundtrips_runs() {
+        let original = run("a1b2c3d", vec![timing(1, 100.0, Some(200.0))]);
+        let line = JsonValue::from(&original).stringify().unwrap();
+        let parsed = Run::try_from(line.as_str()).unwrap();
+
+        assert_eq!(parsed.commit.as_deref(), Some("a1b2c3d"));
+        assert_eq!(parsed.timestamp, 1_701_388_800);
+        assert_eq!(parsed.timings.data[0].part_2, Some(Stats::single(200.0)));
+    }
+
+    #[test]
+    fn skips_invalid_lines() {
+        let line = JsonValue::from(&run("a", vec![])).stringify().unwrap();
+        let history = History::parse(&format!("{line}\n{{ invalid\n\n{line}\n"));
+        assert_eq!(history.runs.len(), 2);
+    }
+
+    #[test]
+    fn finds_latest_run_of_day() {
+        let history = History {
+            runs: vec![
+                run(
+                    "first",
+                    vec![timing(1, 100.0, None), timing(2, 100.0, None)],
+                ),
+                run("second", vec![timing(1, 50.0, None)]),
+            ],
+        };
+
+        assert_eq!(history.latest(day!(1)).unwrap().0.label(), "second");
+        assert_eq!(history.latest(day!(2)).unwrap().0.label(), "first");
+        assert!(history.latest(day!(3)).is_none());
+    }
+
+    #[test]
+    fn compares_with_latest_run() {
+        let history = History {
+            runs: vec![run("a1b2c3d", vec![timing(1, 100.0, None)])],
+        };
+        let timings = Timings {
+            data: vec![timing(1, 150.0, Some(10.0)), timing(2, 10.0, None)],
+        };
+
+        let changes = compare(&history, &timings);
+        assert_eq!(changes.len(), 1);
+
+        assert_eq!(changes[0].step, "Part 1");
+        assert_eq!(changes[0].since, "a1b2c3d");
+        assert_eq!(changes[0].percent(), 50.0);
+        assert!(changes[0].is_regression(10.0));
+        assert!(!changes[0].is_regression(50.0));
+    }
+
+    #[test]
+    fn compares_total_of_same_steps() {
+        let history = History {
+            runs: vec![run("a1b2c3d", vec![timing(1, 100.0, Some(20.0))])],
+        };
+        let timings = Timings {
+            data: vec![timing(1, 150.0, Some(10.0))],
+        };
+
+        let changes = compare(&history, &timings);
+        assert_eq!(changes.len(), 3);
+
+        assert_eq!(changes[1].step, "Part 2");
+        assert_eq!(changes[1].percent(), -50.0);
+
+        assert_eq!(changes[2].step, "Total");
+        assert_eq!(changes[2].before, 120.0);
+        assert_eq!(changes[2].after, 160.0);
+    }
+}
@@ -1,25 +1,44 @@
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
+pub mod ocr;
+pub mod registry;
+pub mod run_multi;
 pub mod runner;
+pub mod solution;
 
 pub use day::*;
+pub use year::*;
 
+mod answers;
 mod day;
+mod heap;
+mod history;
+mod output;
+mod profile;
+mod puzzle;
 mod readme_benchmarks;
-mod run_multi;
+mod records;
+mod stats;
+mod templates;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file of the day's year to a string. E.g. like `data/2023/inputs/01.txt`.
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd
+        .join("data")
+        .join(day.year().to_string())
+        .join(folder)
+        .join(format!("{day}.txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
@@ -30,6 +49,7 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
         .join("data")
+        .join(day.year().to_string())
         .join(folder)
         .join(format!("{day}-{part}.txt"));
     let f = fs::read_to_string(filepath);
@@ -39,30 +59,125 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// The optional `year = <year>` parameter sets the event year, it defaults to the configured `AOC_YEAR`.
+/// The optional `parse = <fn>` parameter declares a function that parses the input once. Both parts
+/// then receive a reference to the parsed data instead of the input, and parsing is timed separately.
+/// The optional `timeout = <seconds>` parameter limits the wall-clock time of the day in `cargo all` and `cargo time`.
+///
+/// The examples declared in `data/<year>/examples/<day>.toml` are checked by a generated `check_examples` test
+/// and, with `cargo solve <day> --examples`, before the real input is run.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    ($day:expr $(, $($options:tt)*)?) => {
+        $crate::solution!(
+            @options [$day] [$crate::template::Year::configured()] [[part_one, 1] [part_two, 2]] [] []
+            $($($options)*)?
+        );
+    };
+
+    (@options [$day:expr] [$year:expr] [$($parts:tt)*] [$($parse:expr)?] [$($timeout:expr)?] 1 $(, $($rest:tt)*)?) => {
+        $crate::solution!(@options [$day] [$year] [[part_one, 1]] [$($parse)?] [$($timeout)?] $($($rest)*)?);
+    };
+    (@options [$day:expr] [$year:expr] [$($parts:tt)*] [$($parse:expr)?] [$($timeout:expr)?] 2 $(, $($rest:tt)*)?) => {
+        $crate::solution!(@options [$day] [$year] [[part_two, 2]] [$($parse)?] [$($timeout)?] $($($rest)*)?);
+    };
+    (@options [$day:expr] [$_year:expr] [$($parts:tt)*] [$($parse:expr)?] [$($timeout:expr)?] year = $year:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@options [$day] [$crate::year!($year)] [$($parts)*] [$($parse)?] [$($timeout)?] $($($rest)*)?);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+    (@options [$day:expr] [$year:expr] [$($parts:tt)*] [] [$($timeout:expr)?] parse = $parse:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@options [$day] [$year] [$($parts)*] [$parse] [$($timeout)?] $($($rest)*)?);
+    };
+    (@options [$day:expr] [$year:expr] [$($parts:tt)*] [$($parse:expr)?] [] timeout = $timeout:expr $(, $($rest:tt)*)?) => {
+        $crate::solution!(@options [$day] [$year] [$($parts)*] [$($parse)?] [$timeout] $($($rest)*)?);
+    };
+    (@options [$day:expr] [$year:expr] [$($parts:tt)*] [$($parse:expr)?] [$($timeout:expr)?]) => {
+        /// The wall-clock time limit of the current day, used by the registry of all days.
+        #[allow(dead_code)]
+        pub const TIMEOUT: Option<std::time::Duration> =
+            $crate::solution!(@timeout $($timeout)?);
+
+        $crate::solution!(@impl $day, $year, [$($parse)?] $($parts)*);
+    };
+
+    (@timeout) => {
+        None
+    };
+    (@timeout $timeout:expr) => {
+        Some(std::time::Duration::from_secs($timeout))
+    };
+
+    (@impl $day:expr, $year:expr, [] $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day, $year);
+
+        /// Runs the solution of the current day, used by `main` and the registry of all days.
+        pub fn run_day(
+            options: $crate::template::runner::RunOptions,
+        ) -> $crate::template::runner::DayResult {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_file("inputs", DAY);
+            let mut result = DayResult::default();
+            $( result.set_part($part, run_part($func, &input, DAY, $part, options)); )*
+            result
+        }
+
+        /// Solves a part for an example input. Returns `None` if the part is not implemented.
+        #[allow(dead_code)]
+        fn solve_example(input: &str, part: u8) -> Option<$crate::template::solution::Answer> {
+            $( if part == $part {
+                return Some($crate::template::solution::Solution::into_answer($func(input)));
+            } )*
+            None
+        }
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@impl $day:expr, $year:expr, [$parse:expr] $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@common $day, $year);
+
+        /// Runs the solution of the current day, used by `main` and the registry of all days.
+        pub fn run_day(
+            options: $crate::template::runner::RunOptions,
+        ) -> $crate::template::runner::DayResult {
+            use $crate::template::runner::*;
+            let input = $crate::template::read_file("inputs", DAY);
+            let (parsed, stats, heap) = run_parse($parse, input.as_str(), DAY, options);
+            let mut result = DayResult {
+                parse: Some(stats),
+                parse_heap: heap,
+                ..DayResult::default()
+            };
+            $( result.set_part($part, run_part($func, &parsed, DAY, $part, options)); )*
+            result
+        }
+
+        /// Solves a part for an example input. Returns `None` if the part is not implemented.
+        #[allow(dead_code)]
+        fn solve_example(input: &str, part: u8) -> Option<$crate::template::solution::Answer> {
+            $( if part == $part {
+                return Some($crate::template::solution::Solution::into_answer($func(&$parse(input))));
+            } )*
+            None
+        }
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@common $day:expr, $year:expr) => {
         /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
+        pub const DAY: $crate::template::Day = $crate::day!($day, $year);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let options = $crate::template::runner::RunOptions::from_args();
+            if options.examples && !$crate::template::examples::run(DAY, solve_example) {
+                std::process::exit(1);
+            }
+            run_day(options);
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn check_examples() {
+            $crate::template::examples::assert_all(DAY, solve_example);
         }
     };
 }
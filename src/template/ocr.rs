@@ -0,0 +1,204 @@
+//! Reads answers that are drawn as block letters in the font that Advent of Code puzzles use.
+//!
+//! Puzzles draw letters either 6 pixels high (usually 4 wide) or 10 pixels high (6 wide), with lit
+//! pixels as `#` or `█` and dark pixels as `.` or spaces. Letters are separated by dark columns.
+
+/// Letters of the 6 pixels high font. Not every letter of the alphabet has appeared in a puzzle.
+const SMALL_FONT: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', "###\n.#.\n.#.\n.#.\n.#.\n###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...#\n#...#\n.#.#.\n..#..\n..#..\n..#.."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+/// Letters of the 10 pixels high font.
+const LARGE_FONT: [(char, &str); 14] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+fn is_lit(c: char) -> bool {
+    matches!(c, '#' | '█')
+}
+
+/// Reads block letters as text, e.g. `HI`. Returns `None` if the drawing contains a glyph that isn't a known letter.
+#[must_use]
+pub fn recognize(art: &str) -> Option<String> {
+    let rows: Vec<Vec<bool>> = art
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .skip_while(|row: &Vec<bool>| !row.contains(&true))
+        .collect();
+
+    // NOTE: blank lines at the end are as irrelevant as blank lines at the start.
+    let height = rows.iter().rposition(|row| row.contains(&true))? + 1;
+    let rows = &rows[..height];
+
+    let width = rows.iter().map(Vec::len).max()?;
+    let is_dark_column = |x: usize| rows.iter().all(|row| !row.get(x).copied().unwrap_or(false));
+
+    let mut text = String::new();
+    let mut x = 0;
+
+    while x < width {
+        if is_dark_column(x) {
+            x += 1;
+            continue;
+        }
+
+        let start = x;
+        while x < width && !is_dark_column(x) {
+            x += 1;
+        }
+
+        text.push(read_glyph(rows, start, x)?);
+    }
+
+    (!text.is_empty()).then_some(text)
+}
+
+/// Looks up the glyph between the columns `start` and `end` (exclusive) in both fonts.
+fn read_glyph(rows: &[Vec<bool>], start: usize, end: usize) -> Option<char> {
+    let glyph: Vec<String> = rows
+        .iter()
+        .map(|row| {
+            (start..end)
+                .map(|x| {
+                    if row.get(x).copied().unwrap_or(false) {
+                        '#'
+                    } else {
+                        '.'
+                    }
+                })
+                .collect()
+        })
+        .collect();
+    let glyph = glyph.join("\n");
+
+    SMALL_FONT
+        .iter()
+        .chain(LARGE_FONT.iter())
+        .find(|(_, pattern)| *pattern == glyph)
+        .map(|(letter, _)| *letter)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{LARGE_FONT, SMALL_FONT, recognize};
+
+    #[test]
+    fn recognizes_small_letters() {
+        let art = "\
+#..#.###..####.
+#..#..#...#....
+####..#...###..
+#..#..#...#....
+#..#..#...#....
+#..#.###..####.";
+
+        assert_eq!(recognize(art).as_deref(), Some("HIE"));
+    }
+
+    #[test]
+    fn recognizes_blocks_and_spaces() {
+        let art = "\n ██  █   \n█  █ █   \n█  █ █   \n████ █   \n█  █ █   \n█  █ ████\n\n";
+        assert_eq!(recognize(art).as_deref(), Some("AL"));
+    }
+
+    #[test]
+    fn recognizes_large_letters() {
+        let (_, h) = LARGE_FONT.iter().find(|(c, _)| *c == 'H').unwrap();
+        let (_, z) = LARGE_FONT.iter().find(|(c, _)| *c == 'Z').unwrap();
+        let art: Vec<String> = h
+            .lines()
+            .zip(z.lines())
+            .map(|(h, z)| format!("{h}..{z}"))
+            .collect();
+
+        assert_eq!(recognize(&art.join("\n")).as_deref(), Some("HZ"));
+    }
+
+    #[test]
+    fn recognizes_every_letter() {
+        for (letter, pattern) in SMALL_FONT.iter().chain(LARGE_FONT.iter()) {
+            assert_eq!(recognize(pattern), Some(letter.to_string()));
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_glyphs() {
+        assert_eq!(recognize("#.#\n.#.\n#.#"), None);
+        assert_eq!(recognize("....\n...."), None);
+        assert_eq!(recognize(""), None);
+    }
+}
@@ -0,0 +1,90 @@
+/// Output of the runner. Output can be captured per thread, so that days running in parallel can print atomically.
+use std::cell::RefCell;
+use std::fmt::{self, Write as _};
+use std::io::{Write, stdout};
+
+thread_local! {
+    static CAPTURED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Writes to the capture buffer of the current thread if set, to stdout otherwise.
+pub fn write(args: fmt::Arguments) {
+    let is_captured = CAPTURED.with_borrow_mut(|captured| match captured {
+        Some(buffer) => {
+            // NOTE: writing to a `String` can not fail.
+            let _ = buffer.write_fmt(args);
+            true
+        }
+        None => false,
+    });
+
+    if !is_captured {
+        print!("{args}");
+    }
+}
+
+/// Flushes stdout, unless output is captured.
+pub fn flush() {
+    if CAPTURED.with_borrow(Option::is_none) {
+        let _ = stdout().flush();
+    }
+}
+
+/// Runs a function and returns its result together with everything it wrote via [`out!`] and [`outln!`].
+pub fn capture<T>(func: impl FnOnce() -> T) -> (T, String) {
+    CAPTURED.set(Some(String::new()));
+    let result = func();
+    let captured = CAPTURED.take().unwrap_or_default();
+    (result, captured)
+}
+
+/// Like `print!`, but respects output capturing.
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::template::output::write(format_args!($($arg)*))
+    };
+}
+
+/// Like `println!`, but respects output capturing.
+macro_rules! outln {
+    () => {
+        $crate::template::output::write(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::template::output::write(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub(crate) use {out, outln};
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{capture, out, outln};
+
+    #[test]
+    fn captures_output() {
+        let (result, captured) = capture(|| {
+            out!("Part {}: ", 1);
+            outln!("{}", 42);
+            outln!();
+            7
+        });
+
+        assert_eq!(result, 7);
+        assert_eq!(captured, "Part 1: 42\n\n");
+    }
+
+    #[test]
+    fn captures_per_thread() {
+        let (_, captured) = capture(|| {
+            std::thread::spawn(|| capture(|| outln!("other thread")))
+                .join()
+                .unwrap();
+            outln!("this thread");
+        });
+
+        assert_eq!(captured, "this thread\n");
+    }
+}
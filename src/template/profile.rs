@@ -0,0 +1,147 @@
+/// Collapses the samples of `perf script` into folded stacks, the input format of flamegraph tools.
+///
+/// A folded stack is a single line of `;`-separated frames, root first, followed by the number of
+/// samples that hit this stack, e.g. `2025_01;main;part_one;core::iter::sum 42`.
+use std::collections::BTreeMap;
+
+/// Folds the output of `perf script`. Identical stacks are merged, the lines are sorted by stack.
+///
+/// Every sample of `perf script` is a header line with the command name, followed by one indented line
+/// per frame, leaf first, e.g. `    55d0c2 advent_of_code::part_one+0x1f (/path/to/bin)`. Samples are
+/// separated by blank lines.
+#[must_use]
+pub fn fold_perf_script(script: &str) -> String {
+    let mut stacks: BTreeMap<String, u64> = BTreeMap::new();
+    let mut command: Option<String> = None;
+    let mut frames: Vec<String> = vec![];
+
+    let mut flush = |command: &mut Option<String>, frames: &mut Vec<String>| {
+        if let Some(command) = command.take() {
+            let mut stack = vec![command];
+            stack.extend(frames.drain(..).rev());
+            *stacks.entry(stack.join(";")).or_default() += 1;
+        }
+        frames.clear();
+    };
+
+    for line in script.lines() {
+        if line.trim().is_empty() {
+            flush(&mut command, &mut frames);
+        } else if line.starts_with(char::is_whitespace) {
+            if command.is_some() {
+                frames.push(parse_frame(line));
+            }
+        } else {
+            flush(&mut command, &mut frames);
+            // NOTE: lines starting with `#` are comments of the perf header.
+            command = (!line.starts_with('#')).then(|| parse_command(line));
+        }
+    }
+    flush(&mut command, &mut frames);
+
+    stacks
+        .into_iter()
+        .map(|(stack, count)| format!("{stack} {count}\n"))
+        .collect()
+}
+
+/// Reads the command name of a sample header, e.g. `2025_01` from `2025_01 12345 [002] 1.5: 1001 cpu-clock:u:`.
+/// Command names may contain spaces, so everything before the process id is the name.
+fn parse_command(header: &str) -> String {
+    let tokens: Vec<&str> = header.split_whitespace().collect();
+    let pid = tokens
+        .iter()
+        .skip(1)
+        .position(|token| token.split('/').all(|id| id.parse::<u32>().is_ok()))
+        .unwrap_or(0);
+
+    tokens[..=pid].join(" ")
+}
+
+/// Reads the symbol of a frame line, without its offset and binary, e.g. `core::iter::sum` from
+/// `55d0c2 core::iter::sum+0x1f (/path/to/bin)`. Frames without a symbol become `[unknown]`.
+fn parse_frame(line: &str) -> String {
+    let line = line.trim();
+    let symbol = line
+        .split_once(char::is_whitespace)
+        .map_or("", |(_, rest)| rest);
+    let symbol = symbol
+        .rsplit_once(" (")
+        .map_or(symbol, |(symbol, _)| symbol);
+    let symbol = match symbol.rfind("+0x") {
+        Some(offset) => &symbol[..offset],
+        None => symbol,
+    };
+
+    // NOTE: `;` separates frames in the folded format, so it can't be part of a frame.
+    match symbol.trim() {
+        "" => "[unknown]".into(),
+        symbol => symbol.replace(';', ":"),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fold_perf_script, parse_command, parse_frame};
+
+    const SCRIPT: &str = "# ========
+# captured on: Mon Dec  1 06:00:00 2025
+# ========
+2025_01 12345 1000.000001:    1001001 cpu-clock:u:
+\t    55d0c2 core::iter::traits::iterator::Iterator::sum+0x1f (/repo/target/release/2025_01)
+\t    55d0a0 _2025_01::part_one+0x20 (/repo/target/release/2025_01)
+\t    55d000 main+0x10 (/repo/target/release/2025_01)
+
+2025_01 12345 1000.001002:    1001001 cpu-clock:u:
+\t    55d0c3 core::iter::traits::iterator::Iterator::sum+0x20 (/repo/target/release/2025_01)
+\t    55d0a0 _2025_01::part_one+0x20 (/repo/target/release/2025_01)
+\t    55d000 main+0x10 (/repo/target/release/2025_01)
+
+2025_01 12345 1000.002003:    1001001 cpu-clock:u:
+\t    7f0000 [unknown] ([unknown])
+\t    55d000 main+0x10 (/repo/target/release/2025_01)
+";
+
+    #[test]
+    fn folds_identical_stacks() {
+        assert_eq!(
+            fold_perf_script(SCRIPT),
+            "2025_01;main;[unknown] 1\n\
+             2025_01;main;_2025_01::part_one;core::iter::traits::iterator::Iterator::sum 2\n"
+        );
+    }
+
+    #[test]
+    fn folds_empty_script() {
+        assert_eq!(fold_perf_script(""), "");
+        assert_eq!(fold_perf_script("# only a header\n"), "");
+    }
+
+    #[test]
+    fn parses_command_names() {
+        assert_eq!(
+            parse_command("2025_01 12345 1.5: 1001 cpu-clock:u:"),
+            "2025_01"
+        );
+        assert_eq!(
+            parse_command("2025_01 12345/12346 [002] 1.5: cycles:"),
+            "2025_01"
+        );
+        assert_eq!(
+            parse_command("tokio worker 777 1.5: cycles:"),
+            "tokio worker"
+        );
+    }
+
+    #[test]
+    fn parses_frames() {
+        assert_eq!(
+            parse_frame("\t55d0a0 <alloc::vec::Vec<T> as core::ops::Drop>::drop+0x10 (/bin)"),
+            "<alloc::vec::Vec<T> as core::ops::Drop>::drop"
+        );
+        assert_eq!(parse_frame("\t7f0000 [unknown] ([unknown])"), "[unknown]");
+        assert_eq!(parse_frame("\t7f0000"), "[unknown]");
+    }
+}
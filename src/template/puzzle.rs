@@ -0,0 +1,221 @@
+/// Extracts the title, example inputs and answers from a downloaded puzzle description, e.g. `data/2023/puzzles/07.md`.
+///
+/// The description holds one `<article>` per unlocked part. The example input of a part is the
+/// `<pre><code>` block introduced by the word "example", its answer is the emphasized code span that
+/// ends a sentence in the last paragraph about the example.
+use crate::template::aoc_client::{extract_articles, html_to_text};
+
+/// The example input and answer proposed for a part.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PartExample {
+    pub input: Option<String>,
+    pub answer: Option<String>,
+}
+
+/// Proposes an example for every part of a puzzle description. The first entry is part one.
+#[must_use]
+pub fn extract_examples(puzzle: &str) -> Vec<PartExample> {
+    extract_articles(puzzle)
+        .into_iter()
+        .take(2)
+        .map(|article| PartExample {
+            input: find_example_input(article),
+            answer: find_answer(article),
+        })
+        .collect()
+}
+
+/// Reads the title of a puzzle from its heading, e.g. `Trebuchet?!` from `<h2>--- Day 1: Trebuchet?! ---</h2>`.
+#[must_use]
+pub fn extract_title(puzzle: &str) -> Option<String> {
+    let start = puzzle.find("<h2")?;
+    let heading = &puzzle[start..];
+    let heading = &heading[heading.find('>')? + 1..heading.find("</h2>")?];
+
+    let title = html_to_text(heading);
+    let title = title.trim().trim_matches('-').trim();
+    let title = title.split_once(": ").map_or(title, |(_, title)| title);
+
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Picks the first code block that is introduced as an example, or the longest code block otherwise.
+fn find_example_input(article: &str) -> Option<String> {
+    let mut blocks: Vec<(&str, &str)> = vec![];
+    let mut rest = article;
+
+    while let Some(start) = rest.find("<pre><code>") {
+        let intro = &rest[..start];
+        let code = &rest[start + "<pre><code>".len()..];
+        let Some(end) = code.find("</code></pre>") else {
+            break;
+        };
+        blocks.push((intro, &code[..end]));
+        rest = &code[end..];
+    }
+
+    let is_example = |intro: &str| {
+        // NOTE: only look at the paragraph directly before the block.
+        let paragraph = intro.rsplit("<p>").next().unwrap_or(intro);
+        paragraph.to_lowercase().contains("example")
+    };
+
+    blocks
+        .iter()
+        .find(|(intro, _)| is_example(intro))
+        .or_else(|| blocks.iter().max_by_key(|(_, code)| code.len()))
+        .map(|(_, code)| html_to_text(code))
+        .filter(|input| !input.trim().is_empty())
+}
+
+/// Picks the emphasized code span that states the answer of the example. That is the last paragraph
+/// that mentions the example and emphasizes code, or the last paragraph that emphasizes code otherwise.
+/// Within it, the last span that ends a sentence is preferred over intermediate values that follow it.
+fn find_answer(article: &str) -> Option<String> {
+    let paragraphs: Vec<(&str, Vec<(&str, bool)>)> = article
+        .split("<p>")
+        .map(|text| (text, emphasized_code(text)))
+        .filter(|(_, spans)| !spans.is_empty())
+        .collect();
+
+    let (_, paragraph) = paragraphs
+        .iter()
+        .rfind(|(text, _)| text.to_lowercase().contains("example"))
+        .or_else(|| paragraphs.last())?;
+
+    paragraph
+        .iter()
+        .rfind(|(_, ends_sentence)| *ends_sentence)
+        .or_else(|| paragraph.last())
+        .map(|(code, _)| html_to_text(code).trim().to_string())
+        .filter(|answer| !answer.is_empty())
+}
+
+/// Finds the emphasized code spans of a paragraph, both `<code><em>` and `<em><code>`, in order.
+/// Returns the code of every span and whether the span ends a sentence.
+fn emphasized_code(paragraph: &str) -> Vec<(&str, bool)> {
+    const TAGS: [(&str, &str); 2] = [
+        ("<code><em>", "</em></code>"),
+        ("<em><code>", "</code></em>"),
+    ];
+
+    let mut spans = vec![];
+    let mut rest = paragraph;
+
+    while let Some((start, open, close)) = TAGS
+        .into_iter()
+        .filter_map(|(open, close)| Some((rest.find(open)?, open, close)))
+        .min_by_key(|(start, _, _)| *start)
+    {
+        let code = &rest[start + open.len()..];
+        let Some(end) = code.find(close) else {
+            break;
+        };
+
+        rest = code[end + close.len()..].trim_start();
+        let ends_sentence = rest.starts_with(['.', '!', '?']) || rest.starts_with("</p>");
+        spans.push((&code[..end], ends_sentence));
+    }
+
+    spans
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PartExample, extract_examples, extract_title};
+
+    const PUZZLE: &str = r#"<article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2>
+<p>The newly-improved calibration document consists of lines of text.</p>
+<pre><code>not the example</code></pre>
+<p>For example:</p>
+<pre><code>1abc2
+pqr3stu8vwx
+a1b2c3d4e5f
+treb7uchet
+</code></pre>
+<p>In this example, the calibration values of these four lines are <code>12</code>, <code>38</code>, <code>15</code>, and <code>77</code>. Adding these together produces <code><em>142</em></code>.</p>
+</article>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<p>Equipped with this new information, you now need to find the real first and last digit on each line. For example:</p>
+<pre><code>two1nine
+eightwothree
+x&lt;y
+</code></pre>
+<p>Adding these together produces <em><code>281</code></em>.</p>
+</article>"#;
+
+    #[test]
+    fn extracts_examples_of_both_parts() {
+        let examples = extract_examples(PUZZLE);
+
+        assert_eq!(
+            examples,
+            vec![
+                PartExample {
+                    input: Some("1abc2\npqr3stu8vwx\na1b2c3d4e5f\ntreb7uchet\n".into()),
+                    answer: Some("142".into()),
+                },
+                PartExample {
+                    input: Some("two1nine\neightwothree\nx<y\n".into()),
+                    answer: Some("281".into()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn prefers_answer_over_later_values() {
+        let puzzle = r#"<article><p>For example:</p>
+<pre><code>1 2 3
+</code></pre>
+<p>In this example, the sum of every line is <code><em>6</em></code>. After that, the <code><em>3</em></code> largest values are kept for the next step, which starts at <code><em>1</em></code> on the left.</p>
+<p>Later, the elves will need <code><em>12</em></code>.</p>
+<p><em>What is the sum of every line?</em></p>
+</article>"#;
+
+        assert_eq!(
+            extract_examples(puzzle),
+            vec![PartExample {
+                input: Some("1 2 3\n".into()),
+                answer: Some("6".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn extracts_title() {
+        assert_eq!(extract_title(PUZZLE).as_deref(), Some("Trebuchet?!"));
+        assert_eq!(extract_title("<article><p>No heading.</p></article>"), None);
+    }
+
+    #[test]
+    fn falls_back_to_longest_block() {
+        let puzzle = "<article><p>Consider:</p><pre><code>ab</code></pre><p>and</p><pre><code>abcd</code></pre><p>No answer here.</p></article>";
+
+        assert_eq!(
+            extract_examples(puzzle),
+            vec![PartExample {
+                input: Some("abcd".into()),
+                answer: None,
+            }]
+        );
+    }
+
+    #[test]
+    fn handles_parts_without_examples() {
+        let puzzle = "<article><p>Part one.</p></article><article><p>Now it's <code><em>done</em></code>.</p></article>";
+
+        assert_eq!(
+            extract_examples(puzzle),
+            vec![
+                PartExample::default(),
+                PartExample {
+                    input: None,
+                    answer: Some("done".into()),
+                },
+            ]
+        );
+    }
+}
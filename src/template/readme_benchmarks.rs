@@ -2,8 +2,11 @@
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
 use std::{fs, io};
 
-use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::Year;
+use crate::template::heap::{HeapStats, format_bytes};
+use crate::template::run_multi::get_path_for_bin;
+use crate::template::stats::{Stats, format_nanos};
+use crate::template::timings::{Timing, Timings};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -25,11 +28,6 @@ pub struct TablePosition {
     pos_end: usize,
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
-}
-
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     let matches: Vec<_> = readme.match_indices(MARKER).collect();
 
@@ -52,38 +50,130 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Formats the statistics of a part as a table cell, e.g. `` `1.2ms` ±0.1ms · p95 1.4ms ``.
+fn format_cell(stats: Option<Stats>) -> String {
+    let Some(stats) = stats else {
+        return "`-`".into();
+    };
+
+    let mut cell = format!("`{}`", format_nanos(stats.median));
+
+    if stats.samples > 1 {
+        cell.push_str(&format!(
+            " ±{} · p95 {}",
+            format_nanos(stats.stddev),
+            format_nanos(stats.p95)
+        ));
+    }
+
+    if stats.outliers > 0 {
+        cell.push_str(" ⚠");
+    }
+
+    cell
+}
+
+/// Formats the heap usage of a day as a table cell, e.g. `` `1.5 KiB` peak · 12 allocations ``.
+fn format_heap_cell(heap: Option<HeapStats>) -> String {
+    let Some(heap) = heap else {
+        return "`-`".into();
+    };
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    format!(
+        "`{}` peak · {} allocations",
+        format_bytes(heap.peak),
+        heap.allocations
+    )
+}
+
+fn construct_year_table(prefix: &str, year: Year, timings: Vec<Timing>) -> Vec<String> {
+    let timings = Timings { data: timings };
+    let total_millis = timings.total_millis();
+
+    let header = format!("{prefix} {year} Benchmarks");
+
+    // NOTE: only years with solutions that parse separately get a parse column,
+    // only years benchmarked with the `heap-stats` feature get a heap column.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    let has_heap = timings.data.iter().any(|t| t.heap.is_some());
+
+    let mut lines: Vec<String> = vec![header, String::new()];
+
+    let mut columns = vec!["Day"];
+    if has_parse {
+        columns.push("Parse");
+    }
+    columns.extend(["Part 1", "Part 2"]);
+    if has_heap {
+        columns.push("Heap");
+    }
+
+    lines.push(format!("| {} |", columns.join(" | ")));
+    lines.push(format!("| {}  |", vec![":---:"; columns.len()].join(" | ")));
 
     for timing in timings.data {
         let path = get_path_for_bin(timing.day);
+        let parse = if has_parse {
+            format!(" {} |", format_cell(timing.parse))
+        } else {
+            String::new()
+        };
+        let heap = if has_heap {
+            format!(" {} |", format_heap_cell(timing.heap))
+        } else {
+            String::new()
+        };
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) |{} {} | {} |{}",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            parse,
+            format_cell(timing.part_1),
+            format_cell(timing.part_2),
+            heap
         ));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+    lines.push(String::new());
+    lines.push("_Median ± standard deviation, ⚠ marks parts with outliers._".into());
+
+    lines
+}
+
+/// Builds one table per year, the most recent year first.
+fn construct_table(prefix: &str, timings: Timings) -> String {
+    let mut years: Vec<Year> = timings.data.iter().map(|t| t.day.year()).collect();
+    years.sort_unstable_by(|a, b| b.cmp(a));
+    years.dedup();
+
+    let mut lines: Vec<String> = vec![MARKER.into()];
+
+    for (i, year) in years.into_iter().enumerate() {
+        if i > 0 {
+            lines.push(String::new());
+        }
+
+        let year_timings = timings
+            .data
+            .iter()
+            .filter(|t| t.day.year() == year)
+            .cloned()
+            .collect();
+
+        lines.extend(construct_year_table(prefix, year, year_timings));
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn update_content(s: &mut String, timings: Timings) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", timings);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
@@ -91,8 +181,7 @@ fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result
 pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings)?;
     fs::write(path, &readme)?;
     Ok(())
 }
@@ -100,28 +189,37 @@ pub fn update(timings: Timings) -> Result<(), Error> {
 #[cfg(feature = "test_lib")]
 mod tests {
     use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use crate::{
+        day, template::heap::HeapStats, template::stats::Stats, template::timings::Timing,
+        template::timings::Timings, year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
-                    total_nanos: 3e+10,
+                    day: day!(1, year!(2025)),
+                    parse: None,
+                    part_1: Some(Stats::single(10_000_000.0)),
+                    part_2: Some(Stats::single(20_000_000.0)),
+                    total_nanos: 3e+7,
+                    heap: None,
                 },
                 Timing {
-                    day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
-                    total_nanos: 7e+10,
+                    day: day!(2, year!(2025)),
+                    parse: None,
+                    part_1: Some(Stats::single(30_000_000.0)),
+                    part_2: Some(Stats::single(40_000_000.0)),
+                    total_nanos: 7e+7,
+                    heap: None,
                 },
                 Timing {
-                    day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
-                    total_nanos: 9e+10,
+                    day: day!(4, year!(2025)),
+                    parse: None,
+                    part_1: Some(Stats::single(40_000_000.0)),
+                    part_2: Some(Stats::single(50_000_000.0)),
+                    total_nanos: 9e+7,
+                    heap: None,
                 },
             ],
         }
@@ -131,53 +229,132 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(&mut s, get_mock_timings()).unwrap();
+        assert_eq!(s.contains("## 2025 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings()).unwrap();
+        update_content(&mut s, get_mock_timings()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        assert_eq!(
+            s.matches("## 2025 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
     }
 
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings()).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "## 2025 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2025_01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/2025_02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2025_04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
+            "",
+            "_Median ± standard deviation, ⚠ marks parts with outliers._",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_benchmarks_for_multiple_years() {
+        let mut timings = get_mock_timings();
+        timings.data.push(Timing {
+            day: day!(20, year!(2023)),
+            parse: None,
+            part_1: Some(Stats::single(1_000_000.0)),
+            part_2: Some(Stats::single(2_000_000.0)),
+            total_nanos: 3e+6,
+            heap: None,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings).unwrap();
+
+        let pos_2025 = s.find("## 2025 Benchmarks").unwrap();
+        let pos_2023 = s.find("## 2023 Benchmarks").unwrap();
+        assert!(pos_2025 < pos_2023);
+        assert!(s.contains("| [Day 20](./src/bin/2023_20.rs) | `1.0ms` | `2.0ms` |"));
+        assert!(s.contains("**Total: 3.00ms**"));
+        assert!(s.contains("**Total: 190.00ms**"));
+    }
+
+    #[test]
+    fn format_benchmark_statistics() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1 = Some(Stats {
+            samples: 100,
+            min: 9_000_000.0,
+            median: 10_000_000.0,
+            mean: 10_500_000.0,
+            p95: 12_000_000.0,
+            stddev: 500_000.0,
+            outliers: 2,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings).unwrap();
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2025_01.rs) | `10.0ms` ±500.0µs · p95 12.0ms ⚠ | `20.0ms` |"
+        ));
+    }
+
+    #[test]
+    fn format_benchmarks_with_parse_timings() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some(Stats::single(5_000_000.0));
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/2025_01.rs) | `-` | `10.0ms` | `20.0ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2025_02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |"));
+    }
+
+    #[test]
+    fn format_benchmarks_with_heap_stats() {
+        let mut timings = get_mock_timings();
+        timings.data[0].heap = Some(HeapStats {
+            allocations: 12,
+            bytes: 4096,
+            peak: 1536,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings).unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Heap |"));
+        assert!(s.contains("| :---: | :---: | :---: | :---:  |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2025_01.rs) | `10.0ms` | `20.0ms` | `1.5 KiB` peak · 12 allocations |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/2025_02.rs) | `30.0ms` | `40.0ms` | `-` |"));
+    }
 }
@@ -0,0 +1,221 @@
+/// Machine-readable output, printed as one JSON object per line with `--format json`.
+use std::collections::HashMap;
+
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+use crate::template::heap::HeapStats;
+use crate::template::output::outln;
+use crate::template::stats::Stats;
+
+/// The step of a day that a record describes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Kind {
+    /// The shared `parse` step of a day.
+    Parse,
+    /// A solution part, i.e. 1 or 2.
+    Part(u8),
+    /// A day as a whole, printed after its steps.
+    Day,
+}
+
+/// The outcome of a step.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Status {
+    /// The step ran and, for parts, returned an answer.
+    Ok,
+    /// The part ran and returned `None`.
+    Unsolved,
+    /// The part ran and returned an error, which is the message of the record.
+    Failed,
+    /// The day panicked.
+    Panicked,
+    /// The day exceeded its time limit.
+    TimedOut,
+    /// The day has no solution yet.
+    NotScaffolded,
+    /// The day was not benched because an earlier day timed out.
+    Skipped,
+}
+
+impl Status {
+    fn as_str(self) -> &'static str {
+        match self {
+            Status::Ok => "ok",
+            Status::Unsolved => "unsolved",
+            Status::Failed => "failed",
+            Status::Panicked => "panicked",
+            Status::TimedOut => "timed_out",
+            Status::NotScaffolded => "not_scaffolded",
+            Status::Skipped => "skipped",
+        }
+    }
+}
+
+/// A single record, e.g. `{"kind":"part","year":2023,"day":7,"part":1,"status":"ok","answer":"42","nanos":1200.0,"samples":10,"message":null}`.
+#[derive(Clone, Debug)]
+pub struct Record<'a> {
+    pub day: Day,
+    pub kind: Kind,
+    pub status: Status,
+    pub answer: Option<&'a str>,
+    pub stats: Option<&'a Stats>,
+    /// Heap usage of the step, with the `heap-stats` feature.
+    pub heap: Option<&'a HeapStats>,
+    /// Details on the status, e.g. the panic message.
+    pub message: Option<&'a str>,
+}
+
+impl Record<'_> {
+    /// Prints the record as a single line of JSON.
+    pub fn print(&self) {
+        // NOTE: serializing an object of plain values can not fail.
+        outln!("{}", JsonValue::from(self).stringify().unwrap());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Record<'_>> for JsonValue {
+    fn from(value: &Record<'_>) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let (kind, part) = match value.kind {
+            Kind::Parse => ("parse", None),
+            Kind::Part(part) => ("part", Some(part)),
+            Kind::Day => ("day", None),
+        };
+
+        map.insert("kind".into(), JsonValue::String(kind.into()));
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.day.year().into_inner())),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::Number(f64::from(value.day.into_inner())),
+        );
+        map.insert(
+            "part".into(),
+            part.map_or(JsonValue::Null, |part| JsonValue::Number(f64::from(part))),
+        );
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .map_or(JsonValue::Null, |answer| JsonValue::String(answer.into())),
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        let (nanos, samples) = match value.stats {
+            Some(stats) => (
+                JsonValue::Number(stats.median),
+                JsonValue::Number(stats.samples as f64),
+            ),
+            None => (JsonValue::Null, JsonValue::Null),
+        };
+
+        map.insert("nanos".into(), nanos);
+        map.insert("samples".into(), samples);
+        map.insert(
+            "heap".into(),
+            value.heap.map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "message".into(),
+            value
+                .message
+                .map_or(JsonValue::Null, |message| JsonValue::String(message.into())),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashMap;
+    use std::str::FromStr;
+
+    use tinyjson::JsonValue;
+
+    use super::{Kind, Record, Status};
+    use crate::template::heap::HeapStats;
+    use crate::template::stats::Stats;
+    use crate::{day, year};
+
+    fn to_map(record: &Record) -> HashMap<String, JsonValue> {
+        let line = JsonValue::from(record).stringify().unwrap();
+        assert!(!line.contains('\n'));
+        JsonValue::from_str(&line)
+            .unwrap()
+            .get::<HashMap<_, _>>()
+            .unwrap()
+            .clone()
+    }
+
+    #[test]
+    fn serializes_part_records() {
+        let stats = Stats {
+            samples: 10,
+            ..Stats::single(1200.0)
+        };
+
+        let map = to_map(&Record {
+            day: day!(7, year!(2023)),
+            kind: Kind::Part(2),
+            status: Status::Ok,
+            answer: Some("42"),
+            stats: Some(&stats),
+            heap: Some(&HeapStats {
+                allocations: 3,
+                bytes: 2048,
+                peak: 1024,
+            }),
+            message: None,
+        });
+
+        assert_eq!(map["kind"], JsonValue::String("part".into()));
+        assert_eq!(map["year"], JsonValue::Number(2023.0));
+        assert_eq!(map["day"], JsonValue::Number(7.0));
+        assert_eq!(map["part"], JsonValue::Number(2.0));
+        assert_eq!(map["status"], JsonValue::String("ok".into()));
+        assert_eq!(map["answer"], JsonValue::String("42".into()));
+        assert_eq!(map["nanos"], JsonValue::Number(1200.0));
+        assert_eq!(map["samples"], JsonValue::Number(10.0));
+
+        let heap = map["heap"].get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(heap["allocations"], JsonValue::Number(3.0));
+        assert_eq!(heap["peak"], JsonValue::Number(1024.0));
+    }
+
+    #[test]
+    fn serializes_day_records() {
+        let map = to_map(&Record {
+            day: day!(7, year!(2023)),
+            kind: Kind::Day,
+            status: Status::Panicked,
+            answer: None,
+            stats: None,
+            heap: None,
+            message: Some("could not open input file"),
+        });
+
+        assert_eq!(map["kind"], JsonValue::String("day".into()));
+        assert_eq!(map["part"], JsonValue::Null);
+        assert_eq!(map["status"], JsonValue::String("panicked".into()));
+        assert_eq!(map["answer"], JsonValue::Null);
+        assert_eq!(map["nanos"], JsonValue::Null);
+        assert_eq!(map["heap"], JsonValue::Null);
+        assert_eq!(
+            map["message"],
+            JsonValue::String("could not open input file".into())
+        );
+    }
+}
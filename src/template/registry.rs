@@ -0,0 +1,76 @@
+/// Registry of all solutions, linked into a single binary by the build script.
+use std::any::Any;
+use std::panic;
+use std::time::Duration;
+
+use crate::template::Day;
+use crate::template::runner::{DayResult, RunOptions};
+
+/// A solution linked into the registry.
+#[derive(Clone, Copy)]
+pub struct Entry {
+    pub day: Day,
+    pub run: fn(RunOptions) -> DayResult,
+    /// The time limit set via `solution!(<day>, timeout = <seconds>)`.
+    pub timeout: Option<Duration>,
+}
+
+/// Looks up the solution of a day.
+pub fn find(registry: &[Entry], day: Day) -> Option<&Entry> {
+    registry.iter().find(|entry| entry.day == day)
+}
+
+impl Entry {
+    /// Runs the solution. Returns the panic message if the solution panicked, e.g. because its input is missing.
+    pub fn run(&self, options: RunOptions) -> Result<DayResult, String> {
+        let run = self.run;
+        panic::catch_unwind(move || run(options)).map_err(|payload| panic_message(&*payload))
+    }
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".into()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Entry;
+    use crate::day;
+    use crate::template::runner::{DayResult, RunOptions};
+
+    fn panicking_day(_: RunOptions) -> DayResult {
+        panic!("could not open input file");
+    }
+
+    fn formatted_panicking_day(_: RunOptions) -> DayResult {
+        panic!("day {} failed", 7);
+    }
+
+    #[test]
+    fn returns_panic_messages() {
+        let entry = |run| Entry {
+            day: day!(1),
+            run,
+            timeout: None,
+        };
+
+        assert_eq!(
+            entry(panicking_day).run(RunOptions::default()).unwrap_err(),
+            "could not open input file"
+        );
+        assert_eq!(
+            entry(formatted_panicking_day)
+                .run(RunOptions::default())
+                .unwrap_err(),
+            "day 7 failed"
+        );
+    }
+}
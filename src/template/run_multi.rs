@@ -1,257 +1,504 @@
-use std::{collections::HashSet, io};
-
+use std::collections::{BTreeMap, HashSet};
+use std::io::stdout;
+use std::num::NonZeroUsize;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+use std::time::Duration;
+
+use crate::template::heap::HeapStats;
+use crate::template::output::{self, out, outln};
+use crate::template::records::{Kind, Record, Status};
+use crate::template::registry::{self, Entry};
+use crate::template::runner::{DayResult, OutputFormat, RunOptions};
 use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
 
-use super::{
-    all_days,
-    timings::{Timing, Timings},
-};
+use super::timings::{Timing, Timings};
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+/// Options that control how a set of days is run.
+#[derive(Clone, Copy, Debug)]
+pub struct MultiOptions {
+    pub run: RunOptions,
+    /// The number of days that run in parallel.
+    pub jobs: NonZeroUsize,
+    /// The time limit of days that don't set their own via `solution!`.
+    pub timeout: Option<Duration>,
+}
 
-    let mut need_space = false;
+impl Default for MultiOptions {
+    fn default() -> Self {
+        Self {
+            run: RunOptions::default(),
+            jobs: NonZeroUsize::MIN,
+            timeout: None,
+        }
+    }
+}
 
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+/// The outcome of running a day.
+#[derive(Clone, Debug)]
+pub enum DayOutcome {
+    Ok(Box<DayResult>),
+    Panicked(String),
+    TimedOut(Duration),
+    NotScaffolded,
+    /// The day was not benched because an earlier day timed out.
+    Skipped,
+}
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+impl DayOutcome {
+    pub fn result(&self) -> Option<&DayResult> {
+        match self {
+            DayOutcome::Ok(result) => Some(result),
+            _ => None,
+        }
+    }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    fn status(&self) -> Status {
+        match self {
+            DayOutcome::Ok(_) => Status::Ok,
+            DayOutcome::Panicked(_) => Status::Panicked,
+            DayOutcome::TimedOut(_) => Status::TimedOut,
+            DayOutcome::NotScaffolded => Status::NotScaffolded,
+            DayOutcome::Skipped => Status::Skipped,
+        }
+    }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    fn message(&self) -> Option<String> {
+        match self {
+            DayOutcome::Panicked(message) => Some(message.clone()),
+            DayOutcome::TimedOut(timeout) => Some(format!("timed out after {timeout:?}")),
+            _ => None,
+        }
+    }
 
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
+    /// Formats the outcome for the summary, e.g. `✖ panicked: could not open input file`.
+    fn describe(&self) -> String {
+        match self {
+            DayOutcome::Ok(_) => "✔ ok".into(),
+            DayOutcome::Panicked(message) => format!("✖ panicked: {message}"),
+            DayOutcome::TimedOut(timeout) => format!("⏱ timed out after {timeout:?}"),
+            DayOutcome::NotScaffolded => "· not scaffolded".into(),
+            DayOutcome::Skipped => "· skipped after a timeout".into(),
+        }
     }
 }
 
-#[allow(dead_code)]
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
-}
+fn print_header(day: Day, options: RunOptions, need_space: &mut bool) {
+    if options.format != OutputFormat::Text {
+        return;
+    }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+    if *need_space {
+        outln!();
     }
+    *need_space = true;
+
+    outln!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    outln!("------");
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Runs a solution, on a separate thread if it has a time limit.
+/// NOTE: threads can not be stopped, so a day that timed out keeps running in the background
+/// until the process exits. Its output is discarded.
+fn run_entry(entry: Entry, options: RunOptions, timeout: Option<Duration>) -> DayOutcome {
+    let Some(timeout) = entry.timeout.or(timeout) else {
+        return match entry.run(options) {
+            Ok(result) => DayOutcome::Ok(Box::new(result)),
+            Err(message) => DayOutcome::Panicked(message),
+        };
+    };
+
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let _ = sender.send(output::capture(|| entry.run(options)));
+    });
+
+    match receiver.recv_timeout(timeout) {
+        Ok((result, captured)) => {
+            out!("{captured}");
+            match result {
+                Ok(result) => DayOutcome::Ok(Box::new(result)),
+                Err(message) => DayOutcome::Panicked(message),
+            }
+        }
+        Err(_) => DayOutcome::TimedOut(timeout),
+    }
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-pub mod child_commands {
-    use super::{Error, get_path_for_bin};
-    use crate::template::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
+/// Runs a day, unless it would be benched while a day that timed out is still running in the
+/// background, which would skew its timings.
+fn run_day(
+    registry: &[Entry],
+    day: Day,
+    options: MultiOptions,
+    timed_out: &AtomicBool,
+) -> DayOutcome {
+    let outcome = match registry::find(registry, day) {
+        Some(_) if options.run.bench && timed_out.load(Ordering::Relaxed) => DayOutcome::Skipped,
+        Some(entry) => run_entry(*entry, options.run, options.timeout),
+        None => DayOutcome::NotScaffolded,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+    if matches!(outcome, DayOutcome::TimedOut(_)) {
+        timed_out.store(true, Ordering::Relaxed);
+    }
+
+    if options.run.format == OutputFormat::Text {
+        if !matches!(outcome, DayOutcome::Ok(_)) {
+            outln!("{}", outcome.describe());
         }
+    } else {
+        let message = outcome.message();
+        Record {
+            day,
+            kind: Kind::Day,
+            status: outcome.status(),
+            answer: None,
+            stats: None,
+            heap: None,
+            message: message.as_deref(),
+        }
+        .print();
+    }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    outcome
+}
+
+/// Runs a set of days in-process. Returns the outcome of every day, in day order.
+///
+/// With more than one job, days run on a pool of worker threads. The output of each day is
+/// captured and printed as a whole, in day order, as soon as all previous days are done.
+///
+/// When benching, the days that start after a day timed out are skipped.
+pub fn run_days(
+    registry: &[Entry],
+    days_to_run: &HashSet<Day>,
+    options: MultiOptions,
+) -> Vec<(Day, DayOutcome)> {
+    let mut outcomes: Vec<(Day, DayOutcome)> = Vec::with_capacity(days_to_run.len());
+
+    let mut need_space = false;
+    let timed_out = AtomicBool::new(false);
 
-        if is_release {
-            args.push("--release");
+    // NOTE: use non-duplicate, sorted day values.
+    let mut days: Vec<Day> = days_to_run.iter().copied().collect();
+    days.sort_unstable();
+
+    if options.jobs.get() == 1 {
+        for day in days {
+            print_header(day, options.run, &mut need_space);
+            outcomes.push((day, run_day(registry, day, options, &timed_out)));
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        return outcomes;
+    }
+
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..options.jobs.get().min(days.len()) {
+            let sender = sender.clone();
+            let (days, next_index, timed_out) = (&days, &next_index, &timed_out);
+
+            scope.spawn(move || {
+                while let Some(day) = days.get(next_index.fetch_add(1, Ordering::Relaxed)) {
+                    let (outcome, captured) =
+                        output::capture(|| run_day(registry, *day, options, timed_out));
+                    if sender.send((*day, outcome, captured)).is_err() {
+                        break;
+                    }
+                }
+            });
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        drop(sender);
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+        let mut pending: BTreeMap<Day, (DayOutcome, String)> = BTreeMap::new();
+        let mut next_day = days.iter().copied().peekable();
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        for (day, outcome, captured) in receiver {
+            pending.insert(day, (outcome, captured));
 
-        let mut output = vec![];
+            while let Some((outcome, captured)) = next_day.peek().and_then(|d| pending.remove(d)) {
+                let day = next_day.next().unwrap();
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+                // NOTE: hold the lock so that a day's block is not interleaved with other output.
+                let _lock = stdout().lock();
+                print_header(day, options.run, &mut need_space);
+                out!("{captured}");
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+                outcomes.push((day, outcome));
+            }
         }
+    });
 
-        thread.join().unwrap();
-        cmd.wait()?;
+    outcomes
+}
 
-        Ok(output)
+/// Prints the outcome of every day.
+fn print_summary(outcomes: &[(Day, DayOutcome)]) {
+    outln!();
+    outln!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    outln!();
+    outln!("| Day | Status |");
+    for (day, outcome) in outcomes {
+        outln!("|  {day} | {} |", outcome.describe());
     }
+}
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+/// Runs a set of days in-process. Returns their timings if the days were benched.
+pub fn run_multi(
+    registry: &[Entry],
+    days_to_run: &HashSet<Day>,
+    options: MultiOptions,
+) -> Option<Timings> {
+    let outcomes = run_days(registry, days_to_run, options);
+    let is_text = options.run.format == OutputFormat::Text;
+
+    if is_text {
+        print_summary(&outcomes);
+    }
 
-        output
+    if options.run.bench {
+        if outcomes
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+            .any(|(_, outcome)| matches!(outcome, DayOutcome::TimedOut(_)))
+        {
+            eprintln!(
+                "Warning: a day timed out and keeps running in the background, so the days after it were skipped and the timings of days that ran alongside it are unreliable."
+            );
+        }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        let timings = Timings {
+            data: outcomes
+                .iter()
+                .filter_map(|(day, outcome)| Some(to_timing(*day, outcome.result()?)))
+                .collect(),
+        };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+        if is_text {
+            let total_millis = timings.total_millis();
+            outln!(
+                "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
 
-                timings.total_nanos += nanos;
-            });
+        Some(timings)
+    } else {
+        None
+    }
+}
 
-        timings
+/// Converts the results of a day into a [`Timing`].
+fn to_timing(day: Day, result: &DayResult) -> Timing {
+    let part_1 = result.part_1.as_ref().map(|p| p.stats.clone());
+    let part_2 = result.part_2.as_ref().map(|p| p.stats.clone());
+
+    let total_nanos = [&result.parse, &part_1, &part_2]
+        .into_iter()
+        .flatten()
+        .map(|stats| stats.median)
+        .sum();
+
+    let heap = HeapStats::combine(
+        [
+            result.parse_heap,
+            result.part_1.as_ref().and_then(|p| p.heap),
+            result.part_2.as_ref().and_then(|p| p.heap),
+        ]
+        .into_iter()
+        .flatten(),
+    );
+
+    Timing {
+        day,
+        parse: result.parse.clone(),
+        part_1,
+        part_2,
+        total_nanos,
+        heap,
     }
+}
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+/// Returns the name of the solution binary for a day, e.g. `2023_07`.
+#[must_use]
+pub fn get_bin_name(day: Day) -> String {
+    format!("{}_{day}", day.year())
+}
+
+#[must_use]
+pub fn get_path_for_bin(day: Day) -> String {
+    format!("./src/bin/{}.rs", get_bin_name(day))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::HashSet;
+    use std::num::NonZeroUsize;
+    use std::thread;
+    use std::time::Duration;
+
+    use super::{DayOutcome, MultiOptions, run_days, to_timing};
+    use crate::day;
+    use crate::template::heap::HeapStats;
+    use crate::template::output::{capture, outln};
+    use crate::template::registry::Entry;
+    use crate::template::runner::{DayResult, PartResult, RunOptions};
+    use crate::template::stats::Stats;
+
+    fn slow_day(_: RunOptions) -> DayResult {
+        thread::sleep(Duration::from_millis(50));
+        outln!("slow day");
+        DayResult::default()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
+    fn fast_day(_: RunOptions) -> DayResult {
+        outln!("fast day");
+        DayResult::default()
     }
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+    fn panicking_day(_: RunOptions) -> DayResult {
+        panic!("could not open input file");
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+    fn entry(day: crate::template::Day, run: fn(RunOptions) -> DayResult) -> Entry {
+        Entry {
+            day,
+            run,
+            timeout: None,
         }
+    }
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    #[test]
+    fn runs_days_in_parallel_in_order() {
+        let registry = [entry(day!(1), slow_day), entry(day!(2), fast_day)];
+        let days = HashSet::from([day!(1), day!(2), day!(3)]);
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+        let options = MultiOptions {
+            jobs: NonZeroUsize::new(3).unwrap(),
+            ..MultiOptions::default()
+        };
+
+        let (outcomes, output) = capture(|| run_days(&registry, &days, options));
+
+        let days: Vec<_> = outcomes.iter().map(|(day, _)| *day).collect();
+        assert_eq!(days, vec![day!(1), day!(2), day!(3)]);
+
+        let position = |s: &str| output.find(s).unwrap();
+        assert!(position("Day 01") < position("slow day"));
+        assert!(position("slow day") < position("Day 02"));
+        assert!(position("Day 02") < position("fast day"));
+        assert!(position("fast day") < position("· not scaffolded"));
+    }
+
+    #[test]
+    fn classifies_outcomes() {
+        let registry = [
+            entry(day!(1), fast_day),
+            entry(day!(2), panicking_day),
+            Entry {
+                timeout: Some(Duration::from_millis(10)),
+                ..entry(day!(3), slow_day)
+            },
+        ];
+        let days = HashSet::from([day!(1), day!(2), day!(3), day!(4)]);
+
+        let (outcomes, output) = capture(|| run_days(&registry, &days, MultiOptions::default()));
+
+        assert!(matches!(outcomes[0].1, DayOutcome::Ok(_)));
+        assert!(
+            matches!(&outcomes[1].1, DayOutcome::Panicked(message) if message == "could not open input file")
+        );
+        assert!(
+            matches!(outcomes[2].1, DayOutcome::TimedOut(timeout) if timeout == Duration::from_millis(10))
+        );
+        assert!(matches!(outcomes[3].1, DayOutcome::NotScaffolded));
+
+        assert!(output.contains("✖ panicked: could not open input file"));
+        assert!(output.contains("⏱ timed out after 10ms"));
+        assert!(!output.contains("slow day"));
+    }
+
+    #[test]
+    fn applies_global_timeout() {
+        let registry = [entry(day!(1), slow_day), entry(day!(2), fast_day)];
+        let days = HashSet::from([day!(1), day!(2)]);
+
+        let options = MultiOptions {
+            timeout: Some(Duration::from_millis(10)),
+            ..MultiOptions::default()
+        };
+
+        let (outcomes, output) = capture(|| run_days(&registry, &days, options));
+
+        assert!(matches!(outcomes[0].1, DayOutcome::TimedOut(_)));
+        assert!(matches!(outcomes[1].1, DayOutcome::Ok(_)));
+        assert!(output.contains("fast day"));
+    }
+
+    #[test]
+    fn skips_benching_after_timeout() {
+        let registry = [
+            entry(day!(1), slow_day),
+            entry(day!(2), fast_day),
+            entry(day!(3), fast_day),
+        ];
+        let days = HashSet::from([day!(1), day!(2), day!(3)]);
+
+        let options = MultiOptions {
+            run: RunOptions {
+                bench: true,
+                ..RunOptions::default()
+            },
+            timeout: Some(Duration::from_millis(10)),
+            ..MultiOptions::default()
+        };
+
+        let (outcomes, output) = capture(|| run_days(&registry, &days, options));
+
+        assert!(matches!(outcomes[0].1, DayOutcome::TimedOut(_)));
+        assert!(matches!(outcomes[1].1, DayOutcome::Skipped));
+        assert!(matches!(outcomes[2].1, DayOutcome::Skipped));
+        assert!(output.contains("· skipped after a timeout"));
+        assert!(!output.contains("fast day"));
+    }
+
+    #[test]
+    fn converts_results_to_timing() {
+        let result = DayResult {
+            parse: Some(Stats::single(1_000.0)),
+            parse_heap: Some(HeapStats {
+                allocations: 1,
+                bytes: 100,
+                peak: 100,
+            }),
+            part_1: Some(PartResult {
+                answer: "42".into(),
+                stats: Stats::single(2_000.0),
+                heap: Some(HeapStats {
+                    allocations: 2,
+                    bytes: 50,
+                    peak: 40,
+                }),
+            }),
+            part_2: None,
+        };
+
+        let timing = to_timing(day!(1), &result);
+        assert_eq!(timing.parse, Some(Stats::single(1_000.0)));
+        assert_eq!(timing.part_1, Some(Stats::single(2_000.0)));
+        assert_eq!(timing.part_2, None);
+        assert_eq!(timing.total_nanos, 3_000.0);
+        assert_eq!(
+            timing.heap,
+            Some(HeapStats {
+                allocations: 3,
+                bytes: 150,
+                peak: 100,
+            })
+        );
     }
 }
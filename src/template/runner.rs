@@ -1,60 +1,344 @@
 /// Encapsulates code that interacts with solution functions.
+use std::error::Error;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{Write, stdout};
-use std::process::Output;
+use std::str::FromStr;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::answers::{Answers, Check, Submission, Verdict};
+use crate::template::aoc_client::AocClientError;
+use crate::template::heap::{self, HeapStats, format_bytes};
+use crate::template::ocr;
+use crate::template::output::{self, out, outln};
+use crate::template::records::{Kind, Record, Status};
+use crate::template::solution::{Answer, Solution};
+use crate::template::stats::{Stats, format_nanos};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_client};
+
+/// How results are written to stdout.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text.
+    #[default]
+    Text,
+    /// One JSON record per line (NDJSON).
+    Json,
+}
+
+impl FromStr for OutputFormat {
+    type Err = OutputFormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(OutputFormatFromStrError),
+        }
+    }
+}
+
+/// An error which can be returned when parsing an [`OutputFormat`].
+#[derive(Debug)]
+pub struct OutputFormatFromStrError;
+
+impl Error for OutputFormatFromStrError {}
+
+impl Display for OutputFormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting an output format of `text` or `json`")
+    }
+}
+
+/// Options that control how a solution is run.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct RunOptions {
+    /// Bench every part instead of running it once.
+    pub bench: bool,
+    /// The part whose result is submitted.
+    pub submit: Option<u8>,
+    pub format: OutputFormat,
+    /// Check the solution against the examples of the day before running the real input.
+    pub examples: bool,
+    /// Repeat a single part for a while instead of running the day, so that a profiler can sample it.
+    pub profile: Option<Profile>,
+}
+
+/// A part that is executed repeatedly for a profiler, see `cargo solve <day> --profile-cpu`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Profile {
+    pub part: u8,
+    pub duration: Duration,
+}
+
+impl RunOptions {
+    /// Reads the options passed to a solution binary, i.e. `--time`, `--submit <part>`, `--format <format>`, `--examples`
+    /// and `--profile <part> --profile-seconds <seconds>`.
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+
+        let value_of = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .map(|i| args.get(i + 1).map(String::as_str).unwrap_or_default())
+        };
+
+        let submit = value_of("--submit").map(|part| match part.parse::<u8>() {
+            Ok(part) => part,
+            Err(_) => {
+                eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+                process::exit(1);
+            }
+        });
+
+        let format =
+            value_of("--format").map_or(OutputFormat::Text, |format| match format.parse() {
+                Ok(format) => format,
+                Err(e) => {
+                    eprintln!("{e}");
+                    process::exit(1);
+                }
+            });
+
+        let profile = value_of("--profile").map(|part| {
+            let seconds = value_of("--profile-seconds").unwrap_or("10");
+            match (part.parse::<u8>(), seconds.parse::<f64>()) {
+                (Ok(part @ 1..=2), Ok(seconds)) if seconds > 0.0 => Profile {
+                    part,
+                    duration: Duration::from_secs_f64(seconds),
+                },
+                _ => {
+                    eprintln!(
+                        "Invalid profile arguments `--profile {part} --profile-seconds {seconds}` passed by `cargo solve --profile-cpu`. Expected a part of 1 or 2 and a positive number of seconds."
+                    );
+                    process::exit(1);
+                }
+            }
+        });
+
+        Self {
+            bench: args.iter().any(|x| x == "--time"),
+            submit,
+            format,
+            examples: args.iter().any(|x| x == "--examples"),
+            profile,
+        }
+    }
+}
+
+/// The result of a part that returned an answer.
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub answer: String,
+    pub stats: Stats,
+    /// Heap usage of the first run, with the `heap-stats` feature.
+    pub heap: Option<HeapStats>,
+}
+
+/// The results of running a day. Parts that were not run or did not return an answer are `None`.
+#[derive(Clone, Debug, Default)]
+pub struct DayResult {
+    pub parse: Option<Stats>,
+    pub parse_heap: Option<HeapStats>,
+    pub part_1: Option<PartResult>,
+    pub part_2: Option<PartResult>,
+}
+
+impl DayResult {
+    pub fn set_part(&mut self, part: u8, result: Option<PartResult>) {
+        match part {
+            1 => self.part_1 = result,
+            _ => self.part_2 = result,
+        }
+    }
+
+    pub fn part(&self, part: u8) -> Option<&PartResult> {
+        match part {
+            1 => self.part_1.as_ref(),
+            _ => self.part_2.as_ref(),
+        }
+    }
+}
+
+pub fn run_part<I: Copy, T: Solution>(
+    func: impl Fn(I) -> T,
+    input: I,
+    day: Day,
+    part: u8,
+    options: RunOptions,
+) -> Option<PartResult> {
+    if let Some(profile) = options.profile {
+        if profile.part == part {
+            run_profiled(func, input, part, profile.duration);
+        }
+        return None;
+    }
 
-pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
+    let is_text = options.format == OutputFormat::Text;
+
+    let solve = |input| func(input).into_answer();
+    let (result, stats, heap) = run_timed(solve, input, options, |result| {
+        if is_text {
+            print_result(result, &part_str, "");
+        }
+    });
+
+    let answer = result.value();
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    if is_text {
+        print_result(&result, &part_str, &format_duration(&stats));
+        print_stats(&stats);
+        print_heap(heap.as_ref());
+    } else {
+        let (status, message) = match &result {
+            Answer::Unsolved => (Status::Unsolved, None),
+            Answer::Error(e) => (Status::Failed, Some(e.as_str())),
+            _ => (Status::Ok, None),
+        };
+
+        Record {
+            day,
+            kind: Kind::Part(part),
+            status,
+            answer: answer.as_deref(),
+            stats: Some(&stats),
+            heap: heap.as_ref(),
+            message,
+        }
+        .print();
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let answer = answer?;
+    let answers = Answers::read_from_file();
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if is_text {
+        print_accepted_check(&answers, &answer, day, part);
     }
+
+    if options.submit == Some(part) {
+        if matches!(&result, Answer::Art(art) if ocr::recognize(art).is_none()) {
+            eprintln!(
+                "Part {part} returned ASCII art that could not be read as letters, not submitting."
+            );
+        } else {
+            submit_result(&answer, day, part, answers);
+        }
+    }
+
+    Some(PartResult {
+        answer,
+        stats,
+        heap,
+    })
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run the `parse` function of a solution and return the parsed data, which is shared by both parts.
+/// Parsing is timed and measured the same way as a solution part.
+pub fn run_parse<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    day: Day,
+    options: RunOptions,
+) -> (T, Stats, Option<HeapStats>) {
+    let is_text = options.format == OutputFormat::Text;
+
+    let (parsed, stats, heap) = run_timed(func, input, options, |_| {
+        if is_text {
+            out!("Parse: ✔");
+        }
+    });
+
+    if is_text {
+        out!("\r");
+        outln!("Parse: ✔{}", format_duration(&stats));
+        print_stats(&stats);
+        print_heap(heap.as_ref());
+    } else {
+        Record {
+            day,
+            kind: Kind::Parse,
+            status: Status::Ok,
+            answer: None,
+            stats: Some(&stats),
+            heap: heap.as_ref(),
+            message: None,
+        }
+        .print();
+    }
+
+    (parsed, stats, heap)
+}
+
+/// Bench any function the same way `--time` benches a solution part, e.g. to compare helpers of the library.
+/// Prints the timing as `<name>: ✔ (<median> @ <n> samples)` followed by the distribution of the samples.
+pub fn bench_fn<I: Copy, T>(name: &str, func: impl Fn(I) -> T, input: I) -> Stats {
+    let options = RunOptions {
+        bench: true,
+        ..RunOptions::default()
+    };
+
+    let (_, stats, heap) = run_timed(func, input, options, |_| out!("{name}: ✔"));
+
+    out!("\r");
+    outln!("{name}: ✔{}", format_duration(&stats));
+    print_stats(&stats);
+    print_heap(heap.as_ref());
+
+    stats
+}
+
+/// Compare a result with the accepted answer of a part, if the part has been solved.
+fn print_accepted_check(answers: &Answers, result: &str, day: Day, part: u8) {
+    match answers.check(day, part, result) {
+        Check::Accepted => outln!("  ✔ matches accepted answer"),
+        Check::Regression(accepted) => outln!("  ✖ regression (accepted answer: {accepted})"),
+        _ => {}
+    }
+}
+
+/// Run a solution part. The behavior differs depending on whether benching is enabled:
+///  1. by default, the function is executed once.
+///  2. with `--time`, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///
+/// With the `heap-stats` feature, the heap usage of the first execution is measured.
 fn run_timed<I: Copy, T>(
     func: impl Fn(I) -> T,
     input: I,
+    options: RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Stats, Option<HeapStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, heap) = heap::measure(|| {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
         func(input)
-    };
+    });
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if options.bench {
+        bench(func, input, &base_time, options.format)
     } else {
-        (base_time, 1)
+        Stats::from_samples(&[base_time]).unwrap()
     };
 
-    (result, run.0, run.1)
+    (result, stats, heap)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+fn bench<I: Copy, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    format: OutputFormat,
+) -> Stats {
+    if format == OutputFormat::Text {
+        out!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        output::flush();
+    }
 
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
@@ -67,101 +351,160 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    // NOTE: the iteration count is clamped to at least 10, so there are always samples.
+    Stats::from_samples(&timers).unwrap()
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Executes a part repeatedly until `duration` has passed, so that a sampling profiler collects enough samples.
+/// Answers are discarded, the part is neither checked nor submitted.
+fn run_profiled<I: Copy, T>(func: impl Fn(I) -> T, input: I, part: u8, duration: Duration) {
+    let timer = Instant::now();
+    let mut iterations: u64 = 0;
+
+    while iterations == 0 || timer.elapsed() < duration {
+        black_box(func(black_box(input)));
+        iterations += 1;
+    }
+
+    outln!(
+        "Part {part}: {ANSI_ITALIC}profiled {iterations} iterations in {}{ANSI_RESET}",
+        format_nanos(timer.elapsed().as_secs_f64() * 1e9)
+    );
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_duration(stats: &Stats) -> String {
+    let median = format_nanos(stats.median);
+    if stats.samples == 1 {
+        format!(" ({median})")
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(" ({median} @ {} samples)", stats.samples)
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+/// Print the distribution of benched samples. Single runs have no distribution worth printing.
+fn print_stats(stats: &Stats) {
+    if stats.samples == 1 {
+        return;
+    }
+
+    let mut line = format!(
+        "  min {} · mean {} · p95 {} · stddev {}",
+        format_nanos(stats.min),
+        format_nanos(stats.mean),
+        format_nanos(stats.p95),
+        format_nanos(stats.stddev)
+    );
+
+    if stats.outliers > 0 {
+        line.push_str(&format!(" · ⚠ {} outliers", stats.outliers));
+    }
+
+    outln!("{ANSI_ITALIC}{line}{ANSI_RESET}");
+}
+
+/// Print the heap usage of a step, if it was measured.
+fn print_heap(heap: Option<&HeapStats>) {
+    let Some(heap) = heap else {
+        return;
+    };
+
+    outln!(
+        "{ANSI_ITALIC}  heap: {} allocations · {} total · peak {}{ANSI_RESET}",
+        heap.allocations,
+        format_bytes(heap.bytes),
+        format_bytes(heap.peak)
+    );
+}
+
+fn print_result(result: &Answer, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
-    match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
-                }
-            } else {
-                let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
-                if is_intermediate_result {
-                    print!("{str}");
-                } else {
-                    print!("\r");
-                    println!("{str}");
-                }
-            }
+    let str = match result {
+        Answer::Number(_) | Answer::Text(_) => {
+            format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}")
         }
-        None => {
-            if is_intermediate_result {
-                print!("{part}: ✖");
-            } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+        Answer::Art(_) => format!("{part}: ▼ {duration_str}"),
+        Answer::Unsolved if is_intermediate_result => format!("{part}: ✖"),
+        Answer::Unsolved => format!("{part}: ✖             "),
+        Answer::Error(e) => format!("{part}: ✖ {e}{duration_str}"),
+    };
+
+    if is_intermediate_result {
+        out!("{str}");
+    } else {
+        out!("\r");
+        outln!("{str}");
+        if let Answer::Art(art) = result {
+            outln!("{art}");
+            match ocr::recognize(art) {
+                Some(text) => outln!("  read as {ANSI_BOLD}{text}{ANSI_RESET}"),
+                None => outln!("  ✖ could not read the letters"),
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
+/// Try to submit one part of the solution if:
+///  1. a session cookie is configured.
+///  2. the answer ledger does not know the answer to be wrong.
+fn submit_result(
+    answer: &str,
     day: Day,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
-
-    if !args.contains(&"--submit".into()) {
-        return None;
+    mut answers: Answers,
+) -> Option<Result<(), AocClientError>> {
+    if let Err(e) = aoc_client::read_session() {
+        eprintln!("{e}");
+        process::exit(1);
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    match answers.check(day, part, answer) {
+        Check::Accepted | Check::Regression(_) => {
+            eprintln!("Part {part} has already been solved, not submitting.");
+            return None;
+        }
+        Check::KnownWrong => {
+            eprintln!("Answer {answer} was already submitted and marked wrong, not submitting.");
+            return None;
+        }
+        Check::LikelyTooHigh(bound) => {
+            eprintln!("⚠ Answer {answer} is likely too high, {bound} was already too high.");
+        }
+        Check::LikelyTooLow(bound) => {
+            eprintln!("⚠ Answer {answer} is likely too low, {bound} was already too low.");
+        }
+        Check::Unknown => {}
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    eprintln!("Submitting result...");
+    let res = aoc_client::submit(day, part, answer);
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    let verdict = match &res {
+        Ok(()) => {
+            eprintln!("🎄 That's the right answer!");
+            Some(Verdict::Correct)
+        }
+        Err(e) => {
+            eprintln!("Submission failed: {e}");
+            match e {
+                AocClientError::WrongAnswer(hint) => Some(Verdict::Wrong(*hint)),
+                _ => None,
+            }
+        }
     };
 
-    if part_submit != part {
-        return None;
-    }
+    if let Some(verdict) = verdict {
+        answers.record(Submission {
+            day,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        });
 
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
-        process::exit(1);
+        if let Err(e) = answers.store_file() {
+            eprintln!("Failed to store answer ledger: {e}");
+        }
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(res)
 }
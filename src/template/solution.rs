@@ -0,0 +1,183 @@
+/// The values that solution parts can return.
+///
+/// A part returns any type that implements [`Solution`], e.g. `Option<u64>`, `Option<String>` or
+/// `Result<u64, E>`. The runner converts it to an [`Answer`], which decides how it is printed,
+/// whether it can be submitted and how errors are reported.
+use std::fmt::Display;
+
+use crate::template::ocr;
+
+/// The answer of a solution part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    /// A numeric answer, e.g. `142`.
+    Number(i128),
+    /// A single-line text answer, e.g. `abc,def`.
+    Text(String),
+    /// Letters drawn on a grid over multiple lines, e.g. with `#` and `.`.
+    /// Read as text with [`ocr::recognize`] before it is checked or submitted.
+    Art(String),
+    /// The part is not implemented or did not find an answer.
+    Unsolved,
+    /// The part failed, with the message of its error.
+    Error(String),
+}
+
+impl Answer {
+    /// The answer as text, `None` for unsolved and failed parts.
+    /// Art is read as letters, or kept as drawn if it contains glyphs that aren't letters.
+    #[must_use]
+    pub fn value(&self) -> Option<String> {
+        match self {
+            Answer::Number(n) => Some(n.to_string()),
+            Answer::Text(s) => Some(s.clone()),
+            Answer::Art(s) => Some(ocr::recognize(s).unwrap_or_else(|| s.clone())),
+            Answer::Unsolved | Answer::Error(_) => None,
+        }
+    }
+
+    /// Converts a text answer to [`Answer::Art`] if it spans multiple lines, and to [`Answer::Text`] otherwise.
+    #[must_use]
+    pub fn from_text(text: impl Into<String>) -> Self {
+        let text = text.into();
+        if text.contains('\n') {
+            Answer::Art(text)
+        } else {
+            Answer::Text(text)
+        }
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{n}"),
+            Answer::Text(s) | Answer::Art(s) => write!(f, "{s}"),
+            Answer::Unsolved => write!(f, "unsolved"),
+            Answer::Error(e) => write!(f, "error: {e}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A value that a solution part can return.
+pub trait Solution {
+    fn into_answer(self) -> Answer;
+}
+
+impl Solution for Answer {
+    fn into_answer(self) -> Answer {
+        self
+    }
+}
+
+macro_rules! impl_number {
+    ($($t:ty),*) => {
+        $(impl Solution for $t {
+            fn into_answer(self) -> Answer {
+                Answer::Number(i128::from(self))
+            }
+        })*
+    };
+}
+
+impl_number!(u8, u16, u32, u64, i8, i16, i32, i64, i128);
+
+/// Numbers that don't fit an `i128` are kept as text.
+macro_rules! impl_wide_number {
+    ($($t:ty),*) => {
+        $(impl Solution for $t {
+            fn into_answer(self) -> Answer {
+                i128::try_from(self).map_or_else(|_| Answer::Text(self.to_string()), Answer::Number)
+            }
+        })*
+    };
+}
+
+impl_wide_number!(u128, usize, isize);
+
+impl Solution for String {
+    fn into_answer(self) -> Answer {
+        Answer::from_text(self)
+    }
+}
+
+impl Solution for &str {
+    fn into_answer(self) -> Answer {
+        Answer::from_text(self)
+    }
+}
+
+impl Solution for char {
+    fn into_answer(self) -> Answer {
+        Answer::Text(self.to_string())
+    }
+}
+
+impl<T: Solution> Solution for Option<T> {
+    fn into_answer(self) -> Answer {
+        self.map_or(Answer::Unsolved, Solution::into_answer)
+    }
+}
+
+impl<T: Solution, E: Display> Solution for Result<T, E> {
+    fn into_answer(self) -> Answer {
+        match self {
+            Ok(value) => value.into_answer(),
+            Err(e) => Answer::Error(e.to_string()),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Answer, Solution};
+
+    #[test]
+    fn converts_numbers() {
+        assert_eq!(Some(142_u64).into_answer(), Answer::Number(142));
+        assert_eq!((-3_i32).into_answer(), Answer::Number(-3));
+        assert_eq!(
+            u128::MAX.into_answer(),
+            Answer::Text("340282366920938463463374607431768211455".into())
+        );
+    }
+
+    #[test]
+    fn converts_text() {
+        assert_eq!("abc,def".into_answer(), Answer::Text("abc,def".into()));
+        assert_eq!(
+            String::from("#..#\n#..#").into_answer(),
+            Answer::Art("#..#\n#..#".into())
+        );
+        assert_eq!('x'.into_answer(), Answer::Text("x".into()));
+    }
+
+    #[test]
+    fn converts_missing_answers_and_errors() {
+        assert_eq!(None::<u64>.into_answer(), Answer::Unsolved);
+
+        let failed: Result<u64, String> = Err("no path found".into());
+        assert_eq!(failed.into_answer(), Answer::Error("no path found".into()));
+
+        let solved: Result<Option<u64>, String> = Ok(Some(7));
+        assert_eq!(solved.into_answer(), Answer::Number(7));
+    }
+
+    #[test]
+    fn reads_values() {
+        assert_eq!(Answer::Number(7).value().as_deref(), Some("7"));
+        assert_eq!(Answer::Art("#\n#".into()).value().as_deref(), Some("#\n#"));
+        assert_eq!(
+            Answer::Art("#..#\n#..#\n####\n#..#\n#..#\n#..#".into())
+                .value()
+                .as_deref(),
+            Some("H")
+        );
+        assert_eq!(Answer::Unsolved.value(), None);
+        assert_eq!(Answer::Error("oops".into()).value(), None);
+    }
+}
@@ -0,0 +1,148 @@
+/// Summary statistics over the samples of a benchmark.
+use std::time::Duration;
+
+/// Statistics of a benchmarked solution part. All durations are in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Stats {
+    pub samples: u128,
+    pub min: f64,
+    pub median: f64,
+    pub mean: f64,
+    pub p95: f64,
+    pub stddev: f64,
+    /// Number of samples outside the Tukey fences (1.5 × IQR beyond the quartiles).
+    pub outliers: usize,
+}
+
+impl Stats {
+    /// Computes statistics from a set of samples. Returns `None` if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        #[allow(clippy::cast_precision_loss)]
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        #[allow(clippy::cast_precision_loss)]
+        let n = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / n;
+
+        let stddev = if nanos.len() > 1 {
+            (nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)).sqrt()
+        } else {
+            0.0
+        };
+
+        let q1 = quantile(&nanos, 0.25);
+        let q3 = quantile(&nanos, 0.75);
+        let iqr = q3 - q1;
+        let (low, high) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+
+        Some(Self {
+            samples: nanos.len() as u128,
+            min: nanos[0],
+            median: quantile(&nanos, 0.5),
+            mean,
+            p95: quantile(&nanos, 0.95),
+            stddev,
+            outliers: nanos.iter().filter(|x| **x < low || **x > high).count(),
+        })
+    }
+
+    /// Creates statistics for a single measurement.
+    pub fn single(nanos: f64) -> Self {
+        Self {
+            samples: 1,
+            min: nanos,
+            median: nanos,
+            mean: nanos,
+            p95: nanos,
+            stddev: 0.0,
+            outliers: 0,
+        }
+    }
+}
+
+/// Linearly interpolated quantile of sorted values.
+fn quantile(sorted: &[f64], q: f64) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let pos = (sorted.len() - 1) as f64 * q;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (lower, upper) = (pos.floor() as usize, pos.ceil() as usize);
+
+    #[allow(clippy::cast_precision_loss)]
+    let weight = pos - lower as f64;
+
+    sorted[lower] + (sorted[upper] - sorted[lower]) * weight
+}
+
+/// Formats nanoseconds the same way as `{:.1?}` formats a [`Duration`].
+pub fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos.round() as u64);
+    format!("{duration:.1?}")
+}
+
+/// Parses a duration formatted via `{:?}` to nanoseconds.
+pub fn parse_nanos(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s.trim() {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+    s.split(postfix).next()?.parse().ok()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Stats, format_nanos, parse_nanos};
+
+    fn samples(nanos: &[u64]) -> Vec<Duration> {
+        nanos.iter().copied().map(Duration::from_nanos).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = Stats::from_samples(&samples(&[10, 12, 11, 13, 14, 12, 11, 100])).unwrap();
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.min, 10.0);
+        assert_eq!(stats.median, 12.0);
+        assert_eq!(stats.mean, 22.875);
+        assert_eq!(stats.outliers, 1);
+        assert!(stats.p95 > 14.0 && stats.p95 < 100.0);
+        assert!((stats.stddev - 31.188).abs() < 0.001);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&samples(&[42])).unwrap();
+        assert_eq!(stats, Stats::single(42.0));
+    }
+
+    #[test]
+    fn handles_no_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn formats_and_parses_durations() {
+        assert_eq!(format_nanos(74.13), "74.0ns");
+        assert_eq!(format_nanos(1_500_000.0), "1.5ms");
+        assert_eq!(parse_nanos("74.13ns"), Some(74.13));
+        assert_eq!(parse_nanos("1.5ms"), Some(1_500_000.0));
+        assert_eq!(parse_nanos("2s"), Some(2_000_000_000.0));
+        assert_eq!(parse_nanos("2µs"), Some(2000.0));
+    }
+}
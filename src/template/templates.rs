@@ -0,0 +1,207 @@
+/// Solution templates that `scaffold` renders, loaded at runtime from `templates/<name>.txt`.
+///
+/// Templates are looked up in the directory set by `AOC_TEMPLATES_DIR` first, then in `templates/`.
+/// Without `--template`, a per-day template `<year>_<day>.txt` is preferred if it exists, then the
+/// template named by `AOC_TEMPLATE`, then `default`.
+use std::fmt::Display;
+use std::path::{Path, PathBuf};
+use std::{env, fs, io};
+
+use crate::template::{Day, run_multi::get_bin_name};
+
+static TEMPLATES_DIR: &str = "templates";
+static DEFAULT_TEMPLATE: &str = "default";
+
+#[derive(Debug)]
+pub enum TemplateError {
+    NotFound {
+        name: String,
+        searched: Vec<PathBuf>,
+    },
+    IO(io::Error),
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::NotFound { name, searched } => {
+                let searched: Vec<String> = searched
+                    .iter()
+                    .map(|path| format!("\"{}\"", path.display()))
+                    .collect();
+                write!(
+                    f,
+                    "could not find template `{name}`, searched {}.",
+                    searched.join(", ")
+                )
+            }
+            TemplateError::IO(e) => write!(f, "could not read template: {e}"),
+        }
+    }
+}
+
+impl From<io::Error> for TemplateError {
+    fn from(e: io::Error) -> Self {
+        TemplateError::IO(e)
+    }
+}
+
+/// The directories templates are looked up in, in order.
+fn template_dirs() -> Vec<PathBuf> {
+    let mut dirs = vec![];
+    if let Some(dir) = env::var_os("AOC_TEMPLATES_DIR").filter(|dir| !dir.is_empty()) {
+        dirs.push(PathBuf::from(dir));
+    }
+    dirs.push(PathBuf::from(TEMPLATES_DIR));
+    dirs
+}
+
+fn find(name: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+    dirs.iter()
+        .map(|dir| dir.join(format!("{name}.txt")))
+        .find(|path| path.is_file())
+}
+
+/// Loads the template to scaffold a day with. Returns the path of the template and its contents.
+pub fn load(name: Option<&str>, day: Day) -> Result<(PathBuf, String), TemplateError> {
+    let dirs = template_dirs();
+
+    let name = match name {
+        Some(name) => name.to_string(),
+        None if find(&get_bin_name(day), &dirs).is_some() => get_bin_name(day),
+        None => env::var("AOC_TEMPLATE")
+            .ok()
+            .filter(|name| !name.trim().is_empty())
+            .unwrap_or_else(|| DEFAULT_TEMPLATE.into()),
+    };
+
+    let path = find(&name, &dirs).ok_or_else(|| TemplateError::NotFound {
+        searched: dirs
+            .iter()
+            .map(|dir| dir.join(format!("{name}.txt")))
+            .collect(),
+        name,
+    })?;
+
+    let contents = fs::read_to_string(&path)?;
+    Ok((path, contents))
+}
+
+/// Lists the names of all available templates.
+#[must_use]
+pub fn list() -> Vec<String> {
+    let mut names: Vec<String> = template_dirs()
+        .iter()
+        .filter_map(|dir| fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.ok()?.path();
+            if path.extension()? != "txt" {
+                return None;
+            }
+            Path::new(path.file_stem()?).to_str().map(String::from)
+        })
+        .collect();
+
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Replaces every `%PLACEHOLDER%` of a template. Returns the rendered template and placeholders that were not known.
+#[must_use]
+pub fn render(template: &str, placeholders: &[(&str, String)]) -> (String, Vec<String>) {
+    let mut rendered = template.to_string();
+    for (placeholder, value) in placeholders {
+        rendered = rendered.replace(&format!("%{placeholder}%"), value);
+    }
+
+    // NOTE: every segment enclosed by two `%` could be a placeholder, the first and last segment can't.
+    let segments: Vec<&str> = rendered.split('%').collect();
+    let mut unknown: Vec<String> = segments
+        .iter()
+        .skip(1)
+        .take(segments.len().saturating_sub(2))
+        .filter(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_uppercase() || c == '_'))
+        .map(|s| format!("%{s}%"))
+        .collect();
+
+    unknown.sort_unstable();
+    unknown.dedup();
+    (rendered, unknown)
+}
+
+/// Infers the return type of a part function from its example answer, defaulting to `u64`.
+#[must_use]
+pub fn answer_type(answer: Option<&str>) -> &'static str {
+    let Some(answer) = answer else {
+        return "u64";
+    };
+
+    if answer.parse::<u64>().is_ok() {
+        "u64"
+    } else if answer.parse::<i64>().is_ok() {
+        "i64"
+    } else if answer.parse::<u128>().is_ok() {
+        "u128"
+    } else if answer.parse::<i128>().is_ok() {
+        "i128"
+    } else {
+        "String"
+    }
+}
+
+/// Formats an example answer as the expected result of a part function, e.g. `Some(142)` or `None`.
+#[must_use]
+pub fn format_expected(answer: Option<&str>) -> String {
+    match answer {
+        Some(answer) if answer_type(Some(answer)) == "String" => {
+            format!("Some(String::from({answer:?}))")
+        }
+        Some(answer) => format!("Some({answer})"),
+        None => "None".into(),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answer_type, format_expected, render};
+
+    #[test]
+    fn renders_placeholders() {
+        let (rendered, unknown) = render(
+            "solution!(%DAY_NUMBER%, year = %YEAR%); // 10 % 3 %TITLE%",
+            &[("DAY_NUMBER", "7".into()), ("YEAR", "2023".into())],
+        );
+
+        assert_eq!(rendered, "solution!(7, year = 2023); // 10 % 3 %TITLE%");
+        assert_eq!(unknown, vec!["%TITLE%".to_string()]);
+
+        let (_, unknown) = render("%TITLE% %DAY% %TITLE% %DAY% %NAME%", &[]);
+        assert_eq!(unknown, vec!["%DAY%", "%NAME%", "%TITLE%"]);
+    }
+
+    #[test]
+    fn infers_answer_types() {
+        assert_eq!(answer_type(None), "u64");
+        assert_eq!(answer_type(Some("142")), "u64");
+        assert_eq!(answer_type(Some("-3")), "i64");
+        assert_eq!(
+            answer_type(Some("340282366920938463463374607431768211455")),
+            "u128"
+        );
+        assert_eq!(answer_type(Some("abc,def")), "String");
+    }
+
+    #[test]
+    fn formats_expected_answers() {
+        assert_eq!(format_expected(None), "None");
+        assert_eq!(format_expected(Some("142")), "Some(142)");
+        assert_eq!(
+            format_expected(Some("a\"b")),
+            "Some(String::from(\"a\\\"b\"))"
+        );
+    }
+}
@@ -1,7 +1,9 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::heap::HeapStats;
+use crate::template::stats::{Stats, parse_nanos};
+use crate::template::{Day, Year};
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
@@ -9,9 +11,13 @@ static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    /// Statistics of the `parse` step, for solutions that parse their input once for both parts.
+    pub parse: Option<Stats>,
+    pub part_1: Option<Stats>,
+    pub part_2: Option<Stats>,
     pub total_nanos: f64,
+    /// Combined heap usage of all steps, if measured with the `heap-stats` feature.
+    pub heap: Option<HeapStats>,
 }
 
 /// Represents benchmark times for a set of days.
@@ -51,7 +57,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -111,11 +117,24 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(value.day.year().into_inner())),
+        );
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+        let parse = value.parse.as_ref().map(JsonValue::from);
+        let part_1 = value.part_1.as_ref().map(JsonValue::from);
+        let part_2 = value.part_2.as_ref().map(JsonValue::from);
+
+        map.insert(
+            "parse".into(),
+            match parse {
+                Some(x) => x,
+                None => JsonValue::Null,
+            },
+        );
 
         map.insert(
             "part_1".into(),
@@ -133,10 +152,93 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "heap".into(),
+            value.heap.as_ref().map_or(JsonValue::Null, JsonValue::from),
+        );
+
         JsonValue::Object(map)
     }
 }
 
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("min".into(), JsonValue::Number(value.min));
+        map.insert("median".into(), JsonValue::Number(value.median));
+        map.insert("mean".into(), JsonValue::Number(value.mean));
+        map.insert("p95".into(), JsonValue::Number(value.p95));
+        map.insert("stddev".into(), JsonValue::Number(value.stddev));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl From<&HeapStats> for JsonValue {
+    fn from(value: &HeapStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "allocations".into(),
+                JsonValue::Number(value.allocations as f64),
+            );
+            map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+            map.insert("peak".into(), JsonValue::Number(value.peak as f64));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+/// Parses the heap statistics of a timing.
+fn parse_heap_stats(value: &JsonValue) -> Option<Option<HeapStats>> {
+    match value {
+        JsonValue::Null => Some(None),
+        JsonValue::Object(map) => {
+            let number = |key: &str| map.get(key).and_then(|v| v.get::<f64>()).copied();
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Some(Some(HeapStats {
+                allocations: number("allocations")? as u64,
+                bytes: number("bytes")? as u64,
+                peak: number("peak")? as u64,
+            }))
+        }
+        _ => None,
+    }
+}
+
+/// Parses the statistics of a part. Timings stored before statistics were tracked are a
+/// preformatted duration string, which is read as a single measurement.
+fn parse_part_stats(value: &JsonValue) -> Option<Option<Stats>> {
+    match value {
+        JsonValue::Null => Some(None),
+        JsonValue::String(s) => parse_nanos(s).map(|nanos| Some(Stats::single(nanos))),
+        JsonValue::Object(map) => {
+            let number = |key: &str| map.get(key).and_then(|v| v.get::<f64>()).copied();
+
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Some(Some(Stats {
+                samples: number("samples")? as u128,
+                min: number("min")?,
+                median: number("median")?,
+                mean: number("mean")?,
+                p95: number("p95")?,
+                stddev: number("stddev")?,
+                outliers: number("outliers")? as usize,
+            }))
+        }
+        _ => None,
+    }
+}
+
 impl TryFrom<&JsonValue> for Timing {
     type Error = String;
 
@@ -145,32 +247,59 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
+        // NOTE: timings stored before multi-year support have no year, assume the configured one.
+        let year = match json.get("year") {
+            None => Year::configured(),
+            Some(v) => v
+                .get::<f64>()
+                .and_then(|year| Year::from_str(&year.to_string()).ok())
+                .ok_or("Expected timing.year to be a valid year.")?,
+        };
+
         let day = json
             .get("day")
             .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
+            .and_then(|day| Day::from_str_in_year(day, year).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // NOTE: timings stored before parse timing was tracked have no `parse` key.
+        let parse = match json.get("parse") {
+            None => None,
+            Some(v) => {
+                parse_part_stats(v).ok_or("Expected timing.parse to be null or statistics.")?
+            }
+        };
+
         let part_1 = json
             .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
+            .and_then(parse_part_stats)
+            .ok_or("Expected timing.part_1 to be null or statistics.")?;
 
         let part_2 = json
             .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+            .and_then(parse_part_stats)
+            .ok_or("Expected timing.part_2 to be null or statistics.")?;
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // NOTE: timings measured without the `heap-stats` feature have no `heap` key.
+        let heap = match json.get("heap") {
+            None => None,
+            Some(v) => {
+                parse_heap_stats(v).ok_or("Expected timing.heap to be null or statistics.")?
+            }
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            parse,
+            part_1,
+            part_2,
             total_nanos,
+            heap,
         })
     }
 }
@@ -180,6 +309,7 @@ impl TryFrom<&JsonValue> for Timing {
 #[cfg(feature = "test_lib")]
 mod tests {
     use crate::day;
+    use crate::template::stats::Stats;
 
     use super::{Timing, Timings};
 
@@ -188,28 +318,38 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    parse: None,
+                    part_1: Some(Stats::single(10_000_000.0)),
+                    part_2: Some(Stats::single(20_000_000.0)),
                     total_nanos: 3e+10,
+                    heap: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(Stats::single(30_000_000.0)),
+                    part_2: Some(Stats::single(40_000_000.0)),
                     total_nanos: 7e+10,
+                    heap: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    parse: None,
+                    part_1: Some(Stats::single(40_000_000.0)),
                     part_2: None,
                     total_nanos: 4e+10,
+                    heap: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{
+            day,
+            template::{stats::Stats, timings::Timings},
+            year,
+        };
 
         #[test]
         fn handles_json_timings() {
@@ -218,9 +358,31 @@ mod tests {
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.parse, None);
+            assert_eq!(timing.part_1, Some(Stats::single(1_000_000.0)));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.heap, None);
+        }
+
+        #[test]
+        fn handles_json_timings_with_year() {
+            let json = r#"{ "data": [{ "year": 2023, "day": "20", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.day, day!(20, year!(2023)));
+            assert_eq!(timing.day.year().into_inner(), 2023);
+        }
+
+        #[test]
+        fn handles_json_statistics() {
+            let json = r#"{ "data": [{ "year": 2023, "day": "20", "part_1": { "samples": 10, "min": 1, "median": 2, "mean": 3, "p95": 4, "stddev": 0.5, "outliers": 1 }, "part_2": null, "total_nanos": 2 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let stats = timings.data[0].part_1.clone().unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.median, 2.0);
+            assert_eq!(stats.stddev, 0.5);
+            assert_eq!(stats.outliers, 1);
         }
 
         #[test]
@@ -247,6 +409,7 @@ mod tests {
 
     mod serialization {
         use super::get_mock_timings;
+        use crate::template::{heap::HeapStats, stats::Stats, timings::Timings};
         use std::collections::HashMap;
         use tinyjson::JsonValue;
 
@@ -266,11 +429,31 @@ mod tests {
                 3
             );
         }
+
+        #[test]
+        fn roundtrips_timings() {
+            let mut timings = get_mock_timings();
+            timings.data[0].parse = Some(Stats::single(5_000_000.0));
+            timings.data[0].heap = Some(HeapStats {
+                allocations: 12,
+                bytes: 4096,
+                peak: 1536,
+            });
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].parse, timings.data[0].parse);
+            assert_eq!(parsed.data[1].parse, None);
+            assert_eq!(parsed.data[0].part_1, timings.data[0].part_1);
+            assert_eq!(parsed.data[2].part_2, None);
+            assert_eq!(parsed.data[0].heap, timings.data[0].heap);
+            assert_eq!(parsed.data[1].heap, None);
+        }
     }
 
     mod is_day_complete {
         use crate::{
             day,
+            template::stats::Stats,
             template::timings::{Timing, Timings},
         };
 
@@ -279,13 +462,15 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    parse: None,
+                    part_1: Some(Stats::single(1_000_000.0)),
+                    part_2: Some(Stats::single(2_000_000.0)),
                     total_nanos: 3_000_000_000_f64,
+                    heap: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +478,15 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    parse: None,
+                    part_1: Some(Stats::single(1_000_000.0)),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    heap: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +494,15 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    heap: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -331,9 +520,11 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    heap: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -351,9 +542,11 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    heap: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -0,0 +1,164 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// The first year Advent of Code took place.
+const FIRST_YEAR: u16 = 2015;
+
+/// The first year with a shortened calendar of 12 days.
+const FIRST_SHORT_YEAR: u16 = 2025;
+
+/// The year used when `AOC_YEAR` is not set or invalid.
+const DEFAULT_YEAR: Year = Year(2025);
+
+/// A valid event year of advent (i.e. an integer starting at 2015).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a valid event year,
+    /// returns [`None`] otherwise.
+    pub const fn new(year: u16) -> Option<Self> {
+        if year < FIRST_YEAR {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    /// Returns the year set via the `AOC_YEAR` environment variable at build time.
+    ///
+    /// The variable is set in `.cargo/config.toml` and acts as the default for the `--year` flag.
+    pub const fn configured() -> Self {
+        let parsed = match option_env!("AOC_YEAR") {
+            Some(s) => match parse_u16(s) {
+                Some(year) => Self::new(year),
+                None => None,
+            },
+            None => None,
+        };
+
+        match parsed {
+            Some(year) => year,
+            None => DEFAULT_YEAR,
+        }
+    }
+
+    /// Returns the number of puzzles in this year's calendar.
+    ///
+    /// Events up to 2024 ran from the 1st to the 25th, later events end on the 12th.
+    pub const fn day_count(self) -> u8 {
+        if self.0 >= FIRST_SHORT_YEAR { 12 } else { 25 }
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+/// Parses a decimal string in a const context.
+const fn parse_u16(s: &str) -> Option<u16> {
+    let bytes = s.as_bytes();
+    if bytes.is_empty() {
+        return None;
+    }
+
+    let mut value: u16 = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if !b.is_ascii_digit() {
+            return None;
+        }
+        value = match value.checked_mul(10) {
+            Some(v) => match v.checked_add((b - b'0') as u16) {
+                Some(v) => v,
+                None => return None,
+            },
+            None => return None,
+        };
+        i += 1;
+    }
+
+    Some(value)
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "expecting a year starting at {FIRST_YEAR}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {
+        const {
+            $crate::template::Year::new($year)
+                .expect("invalid year, expecting a value starting at 2015")
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Year, parse_u16};
+
+    #[test]
+    fn day_count() {
+        assert_eq!(Year::new(2015).unwrap().day_count(), 25);
+        assert_eq!(Year::new(2024).unwrap().day_count(), 25);
+        assert_eq!(Year::new(2025).unwrap().day_count(), 12);
+    }
+
+    #[test]
+    fn rejects_years_before_first_event() {
+        assert_eq!(Year::new(2014), None);
+        assert!("2014".parse::<Year>().is_err());
+        assert_eq!("2015".parse::<Year>().unwrap(), Year(2015));
+    }
+
+    #[test]
+    fn parses_in_const_context() {
+        assert_eq!(parse_u16("2025"), Some(2025));
+        assert_eq!(parse_u16(""), None);
+        assert_eq!(parse_u16("20x5"), None);
+        assert_eq!(parse_u16("99999"), None);
+    }
+}